@@ -0,0 +1,153 @@
+use core::ops::RangeBounds;
+use core::sync::atomic::Ordering;
+#[cfg(target_has_atomic = "16")]
+use core::sync::atomic::{AtomicI16, AtomicU16};
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::{AtomicI32, AtomicU32};
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicI64, AtomicU64};
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::{AtomicI8, AtomicU8};
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicIsize, AtomicUsize};
+
+use super::{for_each_memmove_order, resolve_copy};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// The atomic integer types from `core::sync::atomic` that
+/// [`copy_in_place_atomic`] supports.
+///
+/// This trait is sealed and can't be implemented outside of this crate.
+///
+/// [`copy_in_place_atomic`]: fn.copy_in_place_atomic.html
+pub trait AtomicInteger: private::Sealed {
+    /// The integer type that the atomic type holds.
+    type Value: Copy;
+
+    /// Calls the inherent `load` method.
+    fn load(&self, order: Ordering) -> Self::Value;
+
+    /// Calls the inherent `store` method.
+    fn store(&self, value: Self::Value, order: Ordering);
+}
+
+macro_rules! impl_atomic_integer {
+    ($($width:tt => $atomic:ident($value:ty),)*) => {$(
+        #[cfg(target_has_atomic = $width)]
+        impl private::Sealed for $atomic {}
+
+        #[cfg(target_has_atomic = $width)]
+        impl AtomicInteger for $atomic {
+            type Value = $value;
+
+            fn load(&self, order: Ordering) -> $value {
+                $atomic::load(self, order)
+            }
+
+            fn store(&self, value: $value, order: Ordering) {
+                $atomic::store(self, value, order)
+            }
+        }
+    )*};
+}
+
+impl_atomic_integer! {
+    "8" => AtomicU8(u8),
+    "8" => AtomicI8(i8),
+    "16" => AtomicU16(u16),
+    "16" => AtomicI16(i16),
+    "32" => AtomicU32(u32),
+    "32" => AtomicI32(i32),
+    "64" => AtomicU64(u64),
+    "64" => AtomicI64(i64),
+    "ptr" => AtomicUsize(usize),
+    "ptr" => AtomicIsize(isize),
+}
+
+/// Copies elements within a shared slice of atomic integers, like
+/// [`copy_in_place`].
+///
+/// Each element is copied with one atomic `load` using the `load` ordering and
+/// one atomic `store` using the `store` ordering. The result is correct when
+/// the two ranges overlap, as long as no other thread writes to them
+/// concurrently. The copy as a whole is not atomic.
+///
+/// # Panics
+///
+/// This function will panic if either range exceeds the end of the slice, or if
+/// the end of `src` is before the start. It will also panic if `load` is
+/// `Release` or `AcqRel`, or if `store` is `Acquire` or `AcqRel`, like the
+/// underlying atomic operations.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_in_place_atomic;
+/// use std::sync::atomic::{AtomicU32, Ordering};
+///
+/// let values = [1, 2, 3, 4, 5].map(AtomicU32::new);
+///
+/// copy_in_place_atomic(&values, 0..3, 2, Ordering::Acquire, Ordering::Release);
+///
+/// assert_eq!(values.map(AtomicU32::into_inner), [1, 2, 1, 2, 3]);
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn copy_in_place_atomic<A: AtomicInteger, R: RangeBounds<usize>>(
+    slice: &[A],
+    src: R,
+    dest: usize,
+    load: Ordering,
+    store: Ordering,
+) {
+    let (src_start, count) = match resolve_copy(&src, dest, slice.len()) {
+        Ok(resolved) => resolved,
+        Err(e) => panic!("{}", e),
+    };
+    for_each_memmove_order(src_start, dest, count, |i| {
+        slice[dest + i].store(slice[src_start + i].load(load), store);
+    });
+}
+
+#[cfg(target_has_atomic = "8")]
+#[test]
+fn test_atomic_u8() {
+    let array = b"Hello, World!".map(AtomicU8::new);
+    copy_in_place_atomic(&array, 1..5, 8, Ordering::Relaxed, Ordering::Relaxed);
+    copy_in_place_atomic(&array, 1..5, 2, Ordering::SeqCst, Ordering::SeqCst);
+    assert_eq!(&array.map(AtomicU8::into_inner), b"Heello Wello!");
+}
+
+#[cfg(target_has_atomic = "ptr")]
+#[test]
+fn test_atomic_isize_overlap() {
+    let original = [0isize, -1, -2, -3, -4, -5, -6];
+    for src_start in 0..=original.len() {
+        for src_end in src_start..=original.len() {
+            for dest in 0..=original.len() - (src_end - src_start) {
+                let mut expected = original;
+                super::copy_in_place(&mut expected, src_start..src_end, dest);
+                let array = original.map(AtomicIsize::new);
+                copy_in_place_atomic(
+                    &array,
+                    src_start..src_end,
+                    dest,
+                    Ordering::Acquire,
+                    Ordering::Release,
+                );
+                assert_eq!(array.map(AtomicIsize::into_inner), expected);
+            }
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "32")]
+#[test]
+#[should_panic]
+fn test_atomic_out_of_bounds() {
+    let array = [1, 2, 3].map(AtomicU32::new);
+    copy_in_place_atomic(&array, 1.., 2, Ordering::Relaxed, Ordering::Relaxed);
+}
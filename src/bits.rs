@@ -0,0 +1,273 @@
+use core::cmp;
+use core::ops::RangeBounds;
+
+use super::{copy_in_place, resolve_copy};
+
+/// The order of bits within each byte, for [`copy_bits_in_place`].
+///
+/// [`copy_bits_in_place`]: fn.copy_bits_in_place.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+    /// Bit 0 of a byte is its most significant bit, as in most network
+    /// protocols.
+    MsbFirst,
+    /// Bit 0 of a byte is its least significant bit, as in most bitmaps.
+    LsbFirst,
+}
+
+/// Copies a range of bits from one part of a byte slice to another part of the
+/// same slice, like [`copy_in_place`] with bit indices.
+///
+/// Bit `i` of the slice is bit `i % 8` of byte `i / 8`, where `order` says
+/// which end of the byte bit 0 is. `src_bits` is the range of bits to copy
+/// from, and `dest_bit` is the index of the first bit to copy to. The two
+/// ranges may overlap. Bits outside the destination range are left unchanged.
+///
+/// When the source and destination start at the same offset within a byte, the
+/// whole bytes in between are copied with a single memmove. Otherwise bits are
+/// copied in chunks of up to 56, each loaded and stored as one 64-bit word.
+///
+/// # Panics
+///
+/// This function will panic if either range exceeds the end of the slice, or if
+/// the end of `src_bits` is before the start.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::{copy_bits_in_place, BitOrder};
+/// let mut bytes = [0b1011_0000, 0b0000_0000];
+///
+/// copy_bits_in_place(&mut bytes, 0..4, 6, BitOrder::MsbFirst);
+///
+/// assert_eq!(bytes, [0b1011_0010, 0b1100_0000]);
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn copy_bits_in_place<R: RangeBounds<usize>>(
+    bytes: &mut [u8],
+    src_bits: R,
+    dest_bit: usize,
+    order: BitOrder,
+) {
+    // On targets where the slice has more bits than fit in `usize`, every bit
+    // index that can be passed in is still in bounds.
+    let bit_len = bytes.len().saturating_mul(8);
+    let (src_start, count) = match resolve_copy(&src_bits, dest_bit, bit_len) {
+        Ok(resolved) => resolved,
+        Err(e) => panic!("{}", e),
+    };
+    if count == 0 || src_start == dest_bit {
+        return;
+    }
+    if src_start % 8 == dest_bit % 8 {
+        copy_aligned(bytes, src_start, dest_bit, count, order);
+    } else {
+        copy_unaligned(bytes, src_start, dest_bit, count, order);
+    }
+}
+
+/// Copies bits when the source and destination have the same offset within a
+/// byte: a partial head byte, a memmove of whole bytes, and a partial tail
+/// byte. Copying towards the front goes head first, and otherwise tail first,
+/// so that no source bits are overwritten before they're read.
+fn copy_aligned(bytes: &mut [u8], src: usize, dest: usize, count: usize, order: BitOrder) {
+    let head = cmp::min((8 - src % 8) % 8, count);
+    let middle = (count - head) / 8;
+    let tail = count - head - middle * 8;
+    let copy_head = |bytes: &mut [u8]| copy_bits(bytes, src, dest, head, order);
+    let copy_middle = |bytes: &mut [u8]| {
+        let src_byte = (src + head) / 8;
+        let dest_byte = (dest + head) / 8;
+        copy_in_place(bytes, src_byte..src_byte + middle, dest_byte);
+    };
+    let tail_offset = head + middle * 8;
+    let copy_tail =
+        |bytes: &mut [u8]| copy_bits(bytes, src + tail_offset, dest + tail_offset, tail, order);
+    if dest < src {
+        copy_head(bytes);
+        copy_middle(bytes);
+        copy_tail(bytes);
+    } else {
+        copy_tail(bytes);
+        copy_middle(bytes);
+        copy_head(bytes);
+    }
+}
+
+/// The most bits copied in one chunk. A chunk of this many bits fits in one
+/// 64-bit window at any offset within its first byte.
+const CHUNK_BITS: usize = 56;
+
+/// Copies bits up to `CHUNK_BITS` at a time, front to back when copying
+/// towards the front and back to front otherwise. Each chunk is read completely
+/// before it's written.
+fn copy_unaligned(bytes: &mut [u8], src: usize, dest: usize, count: usize, order: BitOrder) {
+    if dest < src {
+        let mut done = 0;
+        while done < count {
+            let n = cmp::min(CHUNK_BITS, count - done);
+            copy_bits(bytes, src + done, dest + done, n, order);
+            done += n;
+        }
+    } else {
+        let mut remaining = count;
+        while remaining > 0 {
+            let n = cmp::min(CHUNK_BITS, remaining);
+            remaining -= n;
+            copy_bits(bytes, src + remaining, dest + remaining, n, order);
+        }
+    }
+}
+
+/// Copies `n <= CHUNK_BITS` bits, reading all of them before writing any.
+///
+/// The bits are loaded from a 64-bit window starting at the byte that holds
+/// the first source bit, and stored into a window starting at the byte that
+/// holds the first destination bit. Only the partial bytes at either end of
+/// the destination are masked; the bytes in between are overwritten whole.
+fn copy_bits(bytes: &mut [u8], src: usize, dest: usize, n: usize, order: BitOrder) {
+    if n == 0 {
+        return;
+    }
+    let mask = first_bits(n, order);
+    let value = shift_to_first(load_window(bytes, src / 8, order), src % 8, order) & mask;
+    let window = load_window(bytes, dest / 8, order);
+    let dest_mask = shift_from_first(mask, dest % 8, order);
+    let new_bits = shift_from_first(value, dest % 8, order);
+    store_window(bytes, dest / 8, (window & !dest_mask) | new_bits, order);
+}
+
+/// Reads the 8 bytes starting at `byte` as a word, so that the bits of the
+/// window are in `order` from one end of the word to the other. Bytes past the
+/// end of the slice read as zero.
+fn load_window(bytes: &[u8], byte: usize, order: BitOrder) -> u64 {
+    let mut window = [0; 8];
+    match bytes.get(byte..byte + 8) {
+        Some(word) => window.copy_from_slice(word),
+        None => window[..bytes.len() - byte].copy_from_slice(&bytes[byte..]),
+    }
+    match order {
+        BitOrder::MsbFirst => u64::from_be_bytes(window),
+        BitOrder::LsbFirst => u64::from_le_bytes(window),
+    }
+}
+
+/// Writes a word read by `load_window` back to the slice, skipping bytes past
+/// the end.
+fn store_window(bytes: &mut [u8], byte: usize, window: u64, order: BitOrder) {
+    let window = match order {
+        BitOrder::MsbFirst => window.to_be_bytes(),
+        BitOrder::LsbFirst => window.to_le_bytes(),
+    };
+    let end = cmp::min(byte + 8, bytes.len());
+    bytes[byte..end].copy_from_slice(&window[..end - byte]);
+}
+
+/// Returns a window with its first `n` bits set, for `0 < n < 64`.
+fn first_bits(n: usize, order: BitOrder) -> u64 {
+    match order {
+        BitOrder::MsbFirst => !(u64::MAX >> n),
+        BitOrder::LsbFirst => u64::MAX >> (64 - n),
+    }
+}
+
+/// Moves the bits of a window `shift` places towards its first bit.
+fn shift_to_first(window: u64, shift: usize, order: BitOrder) -> u64 {
+    match order {
+        BitOrder::MsbFirst => window << shift,
+        BitOrder::LsbFirst => window >> shift,
+    }
+}
+
+/// Moves the bits of a window `shift` places away from its first bit.
+fn shift_from_first(window: u64, shift: usize, order: BitOrder) -> u64 {
+    match order {
+        BitOrder::MsbFirst => window >> shift,
+        BitOrder::LsbFirst => window << shift,
+    }
+}
+
+#[cfg(test)]
+fn naive_get(bytes: &[u8], bit: usize, order: BitOrder) -> bool {
+    let shift = match order {
+        BitOrder::MsbFirst => 7 - bit % 8,
+        BitOrder::LsbFirst => bit % 8,
+    };
+    (bytes[bit / 8] >> shift) & 1 == 1
+}
+
+#[cfg(test)]
+fn naive_set(bytes: &mut [u8], bit: usize, value: bool, order: BitOrder) {
+    let shift = match order {
+        BitOrder::MsbFirst => 7 - bit % 8,
+        BitOrder::LsbFirst => bit % 8,
+    };
+    bytes[bit / 8] = (bytes[bit / 8] & !(1 << shift)) | ((value as u8) << shift);
+}
+
+#[cfg(test)]
+fn naive_copy_bits(bytes: &mut [u8], src: usize, count: usize, dest: usize, order: BitOrder) {
+    let bits: std::vec::Vec<bool> = (src..src + count)
+        .map(|bit| naive_get(bytes, bit, order))
+        .collect();
+    for (i, &bit) in bits.iter().enumerate() {
+        naive_set(bytes, dest + i, bit, order);
+    }
+}
+
+#[cfg(test)]
+fn check_against_naive(original: &[u8], order: BitOrder) {
+    let bit_len = original.len() * 8;
+    for src_start in 0..=bit_len {
+        for src_end in src_start..=bit_len {
+            let count = src_end - src_start;
+            for dest in 0..=bit_len - count {
+                let mut expected = original.to_vec();
+                naive_copy_bits(&mut expected, src_start, count, dest, order);
+                let mut bytes = original.to_vec();
+                copy_bits_in_place(&mut bytes, src_start..src_end, dest, order);
+                assert_eq!(bytes, expected, "{}..{} -> {}", src_start, src_end, dest);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bits_exhaustive_small() {
+    let pattern = [0b1001_0110, 0b1110_0011, 0b0101_1100, 0b0011_1010];
+    for len in 0..=pattern.len() {
+        check_against_naive(&pattern[..len], BitOrder::MsbFirst);
+        check_against_naive(&pattern[..len], BitOrder::LsbFirst);
+    }
+}
+
+#[test]
+fn test_bits_long_runs() {
+    let original: std::vec::Vec<u8> = (0..40u32).map(|i| (i * 37 + 11) as u8).collect();
+    let bit_len = original.len() * 8;
+    for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+        for &(src_start, src_end, dest) in &[
+            (3, 250, 11),
+            (11, 258, 3),
+            (3, 250, 19),
+            (19, 266, 3),
+            (0, bit_len - 1, 1),
+            (1, bit_len, 0),
+            (5, 300, 8),
+        ] {
+            let mut expected = original.clone();
+            naive_copy_bits(&mut expected, src_start, src_end - src_start, dest, order);
+            let mut bytes = original.clone();
+            copy_bits_in_place(&mut bytes, src_start..src_end, dest, order);
+            assert_eq!(bytes, expected);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_bits_out_of_bounds() {
+    let mut bytes = [0u8; 2];
+    copy_bits_in_place(&mut bytes, 0..4, 13, BitOrder::LsbFirst);
+}
@@ -0,0 +1,264 @@
+use super::copy_in_place;
+
+/// Copies a block of elements within an N-dimensional strided buffer.
+///
+/// The buffer has dimensions `shape`, and the element at index `i` (one
+/// coordinate per dimension) is at offset `sum(i[d] * strides[d])` in `buf`.
+/// The block of size `extent` starting at `src_origin` is copied so that it
+/// starts at `dest_origin`. All of these slices have one entry per dimension,
+/// with the outermost dimension first.
+///
+/// The source and destination may overlap. Each dimension is traversed
+/// backwards when the destination is after the source in that dimension and
+/// forwards otherwise, which makes the result the same as a memmove as long as
+/// no two indices within `shape` map to the same offset. Trailing dimensions
+/// that are contiguous in memory are merged, so that each contiguous run is a
+/// single [`copy_in_place`].
+///
+/// # Panics
+///
+/// This function will panic, without modifying the buffer, if the slices
+/// don't all have the same length, if the block extends past `shape` in any
+/// dimension at either origin, or if any element of `shape` is past the end of
+/// `buf`.
+///
+/// # Examples
+///
+/// Copying a 2×2×2 cube within a 3×3×3 volume:
+///
+/// ```
+/// # use copy_in_place::copy_block_in_place;
+/// let mut volume: Vec<u8> = (0..27).collect();
+///
+/// let (shape, strides) = ([3, 3, 3], [9, 3, 1]);
+/// copy_block_in_place(&mut volume, &shape, &strides, &[0, 0, 0], &[2, 2, 2], &[1, 1, 1]);
+///
+/// assert_eq!(&volume[13..15], &[0, 1]);
+/// assert_eq!(&volume[25..27], &[12, 13]);
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn copy_block_in_place<T: Copy>(
+    buf: &mut [T],
+    shape: &[usize],
+    strides: &[usize],
+    src_origin: &[usize],
+    extent: &[usize],
+    dest_origin: &[usize],
+) {
+    let rank = shape.len();
+    assert!(
+        strides.len() == rank
+            && src_origin.len() == rank
+            && extent.len() == rank
+            && dest_origin.len() == rank,
+        "dimensions don't match"
+    );
+    for d in 0..rank {
+        assert!(
+            fits(src_origin[d], extent[d], shape[d]),
+            "src block is out of bounds"
+        );
+        assert!(
+            fits(dest_origin[d], extent[d], shape[d]),
+            "dest block is out of bounds"
+        );
+    }
+    if shape.contains(&0) {
+        return;
+    }
+    // The offset of the last element of `shape`, which must be in the buffer.
+    let last = shape
+        .iter()
+        .zip(strides)
+        .try_fold(0usize, |offset, (&size, &stride)| {
+            (size - 1)
+                .checked_mul(stride)
+                .and_then(|n| offset.checked_add(n))
+        });
+    assert!(
+        last.is_some_and(|last| last < buf.len()),
+        "shape is out of bounds"
+    );
+    if extent.contains(&0) {
+        return;
+    }
+    // Merge trailing dimensions into a single contiguous run, when each one
+    // steps exactly over the run of the dimensions inside it.
+    let mut inner = rank;
+    let mut run = 1;
+    while inner > 0 && strides[inner - 1] == run {
+        inner -= 1;
+        run *= extent[inner];
+        if extent[inner] != shape[inner] {
+            break;
+        }
+    }
+    let offset =
+        |origin: &[usize]| -> usize { origin.iter().zip(strides).map(|(&i, &s)| i * s).sum() };
+    let walk = Walk {
+        strides: &strides[..inner],
+        extent: &extent[..inner],
+        src_origin: &src_origin[..inner],
+        dest_origin: &dest_origin[..inner],
+        run,
+    };
+    walk.copy(buf, 0, offset(src_origin), offset(dest_origin));
+}
+
+/// Returns `true` if `origin..origin + extent` is within `0..size`.
+fn fits(origin: usize, extent: usize, size: usize) -> bool {
+    origin <= size && extent <= size - origin
+}
+
+/// The outer dimensions of a block copy, the ones not merged into `run`.
+struct Walk<'a> {
+    strides: &'a [usize],
+    extent: &'a [usize],
+    src_origin: &'a [usize],
+    dest_origin: &'a [usize],
+    run: usize,
+}
+
+impl<'a> Walk<'a> {
+    fn copy<T: Copy>(&self, buf: &mut [T], dim: usize, src: usize, dest: usize) {
+        if dim == self.strides.len() {
+            copy_in_place(buf, src..src + self.run, dest);
+            return;
+        }
+        let stride = self.strides[dim];
+        let step = |i: usize| (src + i * stride, dest + i * stride);
+        if self.dest_origin[dim] > self.src_origin[dim] {
+            for i in (0..self.extent[dim]).rev() {
+                let (src, dest) = step(i);
+                self.copy(buf, dim + 1, src, dest);
+            }
+        } else {
+            for i in 0..self.extent[dim] {
+                let (src, dest) = step(i);
+                self.copy(buf, dim + 1, src, dest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn naive_copy_block(
+    buf: &mut [u16],
+    strides: &[usize],
+    src_origin: &[usize],
+    extent: &[usize],
+    dest_origin: &[usize],
+) {
+    let total: usize = extent.iter().product();
+    let mut copies = std::vec::Vec::new();
+    for n in 0..total {
+        // Decompose `n` into an index within the block.
+        let mut rest = n;
+        let mut src = 0;
+        let mut dest = 0;
+        for d in (0..extent.len()).rev() {
+            let i = rest % extent[d];
+            rest /= extent[d];
+            src += (src_origin[d] + i) * strides[d];
+            dest += (dest_origin[d] + i) * strides[d];
+        }
+        copies.push((dest, buf[src]));
+    }
+    for (dest, value) in copies {
+        buf[dest] = value;
+    }
+}
+
+#[cfg(test)]
+fn check_against_naive(shape: &[usize], strides: &[usize], len: usize) {
+    let original: std::vec::Vec<u16> = (0..len as u16).collect();
+    let rank = shape.len();
+    // Try every origin and extent in dimensions up to a small size.
+    let mut cases = std::vec::Vec::new();
+    let mut index = std::vec![0usize; 3 * rank];
+    loop {
+        let (src, rest) = index.split_at(rank);
+        let (extent, dest) = rest.split_at(rank);
+        if (0..rank)
+            .all(|d| fits(src[d], extent[d], shape[d]) && fits(dest[d], extent[d], shape[d]))
+        {
+            cases.push(index.clone());
+        }
+        let mut d = 0;
+        while d < index.len() {
+            index[d] += 1;
+            if index[d] <= shape[d % rank] {
+                break;
+            }
+            index[d] = 0;
+            d += 1;
+        }
+        if d == index.len() {
+            break;
+        }
+    }
+    assert!(!cases.is_empty());
+    for case in cases {
+        let (src, rest) = case.split_at(rank);
+        let (extent, dest) = rest.split_at(rank);
+        let mut expected = original.clone();
+        naive_copy_block(&mut expected, strides, src, extent, dest);
+        let mut buf = original.clone();
+        copy_block_in_place(&mut buf, shape, strides, src, extent, dest);
+        assert_eq!(buf, expected, "{:?} {:?} {:?}", src, extent, dest);
+    }
+}
+
+#[test]
+fn test_block_row_major_3d() {
+    check_against_naive(&[2, 3, 3], &[9, 3, 1], 18);
+}
+
+#[test]
+fn test_block_padded_strides() {
+    // Rows padded to 4 elements and planes padded to 10, so dimensions can't
+    // all be merged.
+    check_against_naive(&[2, 2, 3], &[10, 4, 1], 20);
+}
+
+#[test]
+fn test_block_column_major() {
+    check_against_naive(&[3, 4], &[1, 3], 12);
+}
+
+#[test]
+fn test_block_1d_and_0d() {
+    check_against_naive(&[6], &[1], 6);
+    let mut buf = [1, 2, 3];
+    copy_block_in_place(&mut buf, &[], &[], &[], &[], &[]);
+    assert_eq!(buf, [1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "dest block is out of bounds")]
+fn test_block_out_of_bounds() {
+    let mut buf = [0u8; 27];
+    copy_block_in_place(
+        &mut buf,
+        &[3, 3, 3],
+        &[9, 3, 1],
+        &[0, 0, 0],
+        &[2, 2, 2],
+        &[0, 2, 0],
+    );
+}
+
+#[test]
+#[should_panic(expected = "shape is out of bounds")]
+fn test_block_shape_past_end() {
+    let mut buf = [0u8; 26];
+    copy_block_in_place(
+        &mut buf,
+        &[3, 3, 3],
+        &[9, 3, 1],
+        &[0, 0, 0],
+        &[1, 1, 1],
+        &[0, 0, 0],
+    );
+}
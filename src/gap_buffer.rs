@@ -0,0 +1,424 @@
+use alloc::vec::Vec;
+use core::cmp;
+use core::fmt;
+use core::ops::{Index, RangeBounds};
+use core::str::{self, Utf8Error};
+
+use super::{copy_in_place, resolve_range_or_panic};
+
+/// A growable buffer with a movable gap, for efficient edits near a cursor.
+///
+/// The elements are stored in one `Vec<T>`, split in two by a gap of unused
+/// space. Inserting or deleting at the gap is cheap. Editing anywhere else
+/// first moves the gap there, using [`copy_in_place`] to shift only the
+/// elements between the old and new positions. This is the classic data
+/// structure for the text in an editor, where most edits happen near the
+/// cursor.
+///
+/// All indices are logical indices into the contents, ignoring the gap.
+/// `GapBuffer<u8>` also has helpers for working with UTF-8 text.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::GapBuffer;
+/// let mut buffer = GapBuffer::from(b"Hello, World!".to_vec());
+///
+/// buffer.delete(7..12);
+/// buffer.insert_slice(7, b"Gap");
+/// buffer.insert(0, b'>');
+///
+/// assert_eq!(buffer.to_vec(), b">Hello, Gap!");
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+#[derive(Clone)]
+pub struct GapBuffer<T: Copy> {
+    buf: Vec<T>,
+    gap_start: usize,
+    gap_end: usize,
+}
+
+impl<T: Copy> GapBuffer<T> {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        GapBuffer {
+            buf: Vec::new(),
+            gap_start: 0,
+            gap_end: 0,
+        }
+    }
+
+    /// Returns the number of elements in the buffer, not counting the gap.
+    pub fn len(&self) -> usize {
+        self.buf.len() - (self.gap_end - self.gap_start)
+    }
+
+    /// Returns `true` if the buffer contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index where the gap currently is, which is the index of the
+    /// first element after the gap.
+    pub fn gap_position(&self) -> usize {
+        self.gap_start
+    }
+
+    /// Moves the gap to `pos`, shifting the elements in between across it.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `pos` is greater than `len()`.
+    pub fn move_gap(&mut self, pos: usize) {
+        assert!(pos <= self.len(), "position is out of bounds");
+        if pos < self.gap_start {
+            let count = self.gap_start - pos;
+            copy_in_place(&mut self.buf, pos..self.gap_start, self.gap_end - count);
+            self.gap_start -= count;
+            self.gap_end -= count;
+        } else if pos > self.gap_start {
+            let count = pos - self.gap_start;
+            copy_in_place(
+                &mut self.buf,
+                self.gap_end..self.gap_end + count,
+                self.gap_start,
+            );
+            self.gap_start += count;
+            self.gap_end += count;
+        }
+    }
+
+    /// Inserts `value` at `pos`, moving the gap there.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `pos` is greater than `len()`.
+    pub fn insert(&mut self, pos: usize, value: T) {
+        self.insert_slice(pos, &[value]);
+    }
+
+    /// Inserts a copy of `values` at `pos`, moving the gap there.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `pos` is greater than `len()`.
+    pub fn insert_slice(&mut self, pos: usize, values: &[T]) {
+        self.move_gap(pos);
+        if values.is_empty() {
+            return;
+        }
+        self.reserve_gap(values.len(), values[0]);
+        self.buf[self.gap_start..self.gap_start + values.len()].copy_from_slice(values);
+        self.gap_start += values.len();
+    }
+
+    /// Removes and returns the element at `pos`, moving the gap there.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `pos` is greater than or equal to `len()`.
+    pub fn remove(&mut self, pos: usize) -> T {
+        assert!(pos < self.len(), "position is out of bounds");
+        let value = self[pos];
+        self.delete(pos..pos + 1);
+        value
+    }
+
+    /// Removes the elements in `range`, moving the gap to its start.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `range` exceeds `len()`, or if the end of the
+    /// range is before the start.
+    pub fn delete<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = self.resolve(&range);
+        self.move_gap(start);
+        self.gap_end += end - start;
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if it's out of
+    /// bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.gap_start {
+            self.buf.get(index)
+        } else {
+            self.buf.get(index + (self.gap_end - self.gap_start))
+        }
+    }
+
+    /// Returns the contents before and after the gap.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        (&self.buf[..self.gap_start], &self.buf[self.gap_end..])
+    }
+
+    /// Returns an iterator over all the elements.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        self.range(..)
+    }
+
+    /// Returns an iterator over the elements in `range`, which may span the
+    /// gap. This doesn't move the gap.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `range` exceeds `len()`, or if the end of the
+    /// range is before the start.
+    pub fn range<R: RangeBounds<usize>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        let (start, end) = self.resolve(&range);
+        let (front, back) = self.as_slices();
+        let split = front.len();
+        let front_part = &front[cmp::min(start, split)..cmp::min(end, split)];
+        let back_part = &back[start.saturating_sub(split)..end.saturating_sub(split)];
+        front_part.iter().chain(back_part)
+    }
+
+    /// Copies the contents into a new `Vec`.
+    pub fn to_vec(&self) -> Vec<T> {
+        let (front, back) = self.as_slices();
+        let mut vec = Vec::with_capacity(self.len());
+        vec.extend_from_slice(front);
+        vec.extend_from_slice(back);
+        vec
+    }
+
+    /// Converts the buffer into a `Vec` of its contents, reusing the
+    /// allocation.
+    pub fn into_vec(mut self) -> Vec<T> {
+        let len = self.len();
+        self.move_gap(len);
+        self.buf.truncate(len);
+        self.buf
+    }
+
+    fn resolve<R: RangeBounds<usize>>(&self, range: &R) -> (usize, usize) {
+        resolve_range_or_panic(range, self.len())
+    }
+
+    /// Grows the gap to at least `n` elements, initializing new space with
+    /// `fill`.
+    fn reserve_gap(&mut self, n: usize, fill: T) {
+        if self.gap_end - self.gap_start >= n {
+            return;
+        }
+        let old_capacity = self.buf.len();
+        let back_len = old_capacity - self.gap_end;
+        let new_capacity = cmp::max(cmp::max(old_capacity * 2, self.len() + n), 8);
+        self.buf.resize(new_capacity, fill);
+        copy_in_place(
+            &mut self.buf,
+            self.gap_end..old_capacity,
+            new_capacity - back_len,
+        );
+        self.gap_end = new_capacity - back_len;
+    }
+}
+
+impl GapBuffer<u8> {
+    /// Returns `true` if `pos` is on a UTF-8 char boundary, like
+    /// `str::is_char_boundary`. The start and end of the buffer count as
+    /// boundaries.
+    pub fn is_char_boundary(&self, pos: usize) -> bool {
+        if pos == 0 || pos == self.len() {
+            return true;
+        }
+        match self.get(pos) {
+            Some(&byte) => (byte as i8) >= -0x40,
+            None => false,
+        }
+    }
+
+    /// Inserts `s` at byte index `pos`, moving the gap there.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `pos` is greater than `len()` or not on a char
+    /// boundary.
+    pub fn insert_str(&mut self, pos: usize, s: &str) {
+        assert!(pos <= self.len(), "position is out of bounds");
+        assert!(
+            self.is_char_boundary(pos),
+            "position is not on a char boundary"
+        );
+        self.insert_slice(pos, s.as_bytes());
+    }
+
+    /// Removes the bytes in `range`, moving the gap to its start.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `range` exceeds `len()`, if the end of the
+    /// range is before the start, or if either end is not on a char boundary.
+    pub fn delete_str<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = self.resolve(&range);
+        assert!(
+            self.is_char_boundary(start),
+            "range start is not on a char boundary"
+        );
+        assert!(
+            self.is_char_boundary(end),
+            "range end is not on a char boundary"
+        );
+        self.delete(start..end);
+    }
+
+    /// Returns the contents before and after the gap as strings, or an error if
+    /// either one isn't valid UTF-8. That includes the case where the gap is in
+    /// the middle of a char.
+    pub fn as_str_slices(&self) -> Result<(&str, &str), Utf8Error> {
+        let (front, back) = self.as_slices();
+        Ok((str::from_utf8(front)?, str::from_utf8(back)?))
+    }
+}
+
+impl<T: Copy> Default for GapBuffer<T> {
+    fn default() -> Self {
+        GapBuffer::new()
+    }
+}
+
+impl<T: Copy> From<Vec<T>> for GapBuffer<T> {
+    /// Creates a buffer from the contents of `vec`, with the gap at the end.
+    fn from(vec: Vec<T>) -> Self {
+        let len = vec.len();
+        GapBuffer {
+            buf: vec,
+            gap_start: len,
+            gap_end: len,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for GapBuffer<u8> {
+    /// Creates a buffer from the bytes of `s`, with the gap at the end.
+    fn from(s: &'a str) -> Self {
+        GapBuffer::from(s.as_bytes().to_vec())
+    }
+}
+
+impl<T: Copy> Index<usize> for GapBuffer<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!("index is out of bounds"),
+        }
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for GapBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+struct Xorshift(u64);
+
+#[cfg(test)]
+impl Xorshift {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+#[test]
+fn test_gap_buffer_against_vec() {
+    let mut rng = Xorshift(0x2545_f491_4f6c_dd1d);
+    for _ in 0..100 {
+        let mut buffer = GapBuffer::new();
+        let mut vec: Vec<u32> = Vec::new();
+        for _ in 0..200 {
+            let len = vec.len();
+            match rng.below(6) {
+                0 => {
+                    let pos = rng.below(len + 1);
+                    let value = rng.below(1000) as u32;
+                    buffer.insert(pos, value);
+                    vec.insert(pos, value);
+                }
+                1 => {
+                    let pos = rng.below(len + 1);
+                    let values: Vec<u32> = (0..rng.below(20)).map(|i| i as u32).collect();
+                    buffer.insert_slice(pos, &values);
+                    vec.splice(pos..pos, values);
+                }
+                2 if len > 0 => {
+                    let pos = rng.below(len);
+                    assert_eq!(buffer.remove(pos), vec.remove(pos));
+                }
+                3 => {
+                    let start = rng.below(len + 1);
+                    let end = start + rng.below(len - start + 1);
+                    buffer.delete(start..end);
+                    vec.drain(start..end);
+                }
+                4 => buffer.move_gap(rng.below(len + 1)),
+                _ => {
+                    let start = rng.below(len + 1);
+                    let end = start + rng.below(len - start + 1);
+                    assert!(buffer.range(start..end).eq(&vec[start..end]));
+                    assert!(buffer
+                        .range(start..end)
+                        .rev()
+                        .eq(vec[start..end].iter().rev()));
+                }
+            }
+            assert_eq!(buffer.len(), vec.len());
+            assert_eq!(buffer.to_vec(), vec);
+        }
+        assert_eq!(buffer.into_vec(), vec);
+    }
+}
+
+#[test]
+fn test_gap_buffer_slices() {
+    let mut buffer = GapBuffer::from(b"Hello, World!".to_vec());
+    buffer.move_gap(5);
+    assert_eq!(buffer.as_slices(), (&b"Hello"[..], &b", World!"[..]));
+    assert_eq!(buffer.gap_position(), 5);
+    assert_eq!(buffer[7], b'W');
+    assert_eq!(buffer.get(13), None);
+}
+
+#[test]
+fn test_gap_buffer_str() {
+    let mut buffer = GapBuffer::from("Grüße");
+    assert!(!buffer.is_char_boundary(3));
+    buffer.insert_str(4, "n");
+    buffer.delete_str(5..7);
+    buffer.insert_str(0, "❤ ");
+    assert_eq!(buffer.as_str_slices(), Ok(("❤ ", "Grüne")));
+    buffer.move_gap(1);
+    assert!(buffer.as_str_slices().is_err());
+}
+
+#[test]
+#[should_panic(expected = "position is not on a char boundary")]
+fn test_gap_buffer_insert_str_boundary() {
+    let mut buffer = GapBuffer::from("Grüße");
+    buffer.insert_str(3, "n");
+}
+
+#[test]
+#[should_panic(expected = "range is out of bounds (end: 9, len: 5)")]
+fn test_gap_buffer_delete_out_of_bounds() {
+    let mut buffer = GapBuffer::from(b"Hello".to_vec());
+    buffer.delete(2..9);
+}
+
+#[test]
+#[should_panic(expected = "range end is before range start")]
+fn test_gap_buffer_range_backwards() {
+    let buffer = GapBuffer::from(b"Hello".to_vec());
+    buffer
+        .range((core::ops::Bound::Included(3), core::ops::Bound::Excluded(2)))
+        .count();
+}
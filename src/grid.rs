@@ -0,0 +1,531 @@
+use core::cmp;
+use core::ops::Range;
+
+use super::{clip_span, copy_in_place};
+
+/// A rectangle of elements in a row-major 2D buffer, for
+/// [`copy_rect_in_place`].
+///
+/// Element `(x, y)` of a buffer with row stride `stride` is at index
+/// `y * stride + x`.
+///
+/// [`copy_rect_in_place`]: fn.copy_rect_in_place.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    /// Returns `true` if every element of the rectangle is within a buffer of
+    /// length `len` with row stride `stride`, and no row of the rectangle
+    /// wraps into the next row of the buffer.
+    fn fits(&self, stride: usize, len: usize) -> bool {
+        let right = match self.x.checked_add(self.width) {
+            Some(right) if right <= stride => right,
+            _ => return false,
+        };
+        if self.width == 0 || self.height == 0 {
+            return true;
+        }
+        // The end of the last row.
+        self.y
+            .checked_add(self.height - 1)
+            .and_then(|last_row| last_row.checked_mul(stride))
+            .and_then(|start| start.checked_add(right))
+            .is_some_and(|end| end <= len)
+    }
+}
+
+/// Copies a rectangle of elements within a row-major 2D buffer.
+///
+/// `buf` holds rows of `stride` elements each. The rectangle `src_rect` is
+/// copied so that its top left corner lands at `dest_xy`, given as `(x, y)`.
+/// The source and destination may overlap: rows are copied bottom-up when
+/// moving down and top-down otherwise, and each row is copied with
+/// [`copy_in_place`], which handles horizontal overlap.
+///
+/// # Panics
+///
+/// This function will panic, without modifying the buffer, if either
+/// rectangle extends past the right edge of a row or past the end of `buf`.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::{copy_rect_in_place, Rect};
+/// let mut grid = *b"\
+///     ab..\
+///     cd..\
+///     ....";
+///
+/// let rect = Rect { x: 0, y: 0, width: 2, height: 2 };
+/// copy_rect_in_place(&mut grid, 4, rect, (1, 1));
+///
+/// assert_eq!(&grid, b"\
+///     ab..\
+///     cab.\
+///     .cd.");
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn copy_rect_in_place<T: Copy>(
+    buf: &mut [T],
+    stride: usize,
+    src_rect: Rect,
+    dest_xy: (usize, usize),
+) {
+    let (dest_x, dest_y) = dest_xy;
+    let dest_rect = Rect {
+        x: dest_x,
+        y: dest_y,
+        ..src_rect
+    };
+    assert!(
+        src_rect.fits(stride, buf.len()),
+        "src rect is out of bounds"
+    );
+    assert!(
+        dest_rect.fits(stride, buf.len()),
+        "dest rect is out of bounds"
+    );
+    if src_rect.width == 0 {
+        return;
+    }
+    let copy_row = |buf: &mut [T], row: usize| {
+        let src_start = (src_rect.y + row) * stride + src_rect.x;
+        let dest = (dest_y + row) * stride + dest_x;
+        copy_in_place(buf, src_start..src_start + src_rect.width, dest);
+    };
+    if dest_y > src_rect.y {
+        for row in (0..src_rect.height).rev() {
+            copy_row(buf, row);
+        }
+    } else {
+        for row in 0..src_rect.height {
+            copy_row(buf, row);
+        }
+    }
+}
+
+/// Copies a rectangle within a row-major 2D buffer like
+/// [`copy_rect_in_place`], but clips the copy to the buffer instead of
+/// panicking, and returns the rectangle that was written.
+///
+/// The buffer is treated as a grid of `stride` columns, with a short last row
+/// if `buf.len()` isn't a multiple of `stride`. As in [`copy_rect_in_place`],
+/// the short row can be used by the part of either rectangle that fits in it;
+/// otherwise that row of the copy is dropped. The parts of `src_rect` outside
+/// the grid are ignored. `dest_xy` may be negative, and the parts of the copy that
+/// would land outside the grid on any side are skipped. If nothing is left to
+/// copy, the returned rectangle is empty.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::{copy_rect_in_place_clipped, Rect};
+/// let mut grid = *b"\
+///     abc.\
+///     def.\
+///     ghi.";
+///
+/// let rect = Rect { x: 0, y: 0, width: 3, height: 3 };
+/// let written = copy_rect_in_place_clipped(&mut grid, 4, rect, (2, -1));
+///
+/// assert_eq!(written, Rect { x: 2, y: 0, width: 2, height: 2 });
+/// assert_eq!(&grid, b"\
+///     abde\
+///     degh\
+///     ghi.");
+/// ```
+///
+/// [`copy_rect_in_place`]: fn.copy_rect_in_place.html
+pub fn copy_rect_in_place_clipped<T: Copy>(
+    buf: &mut [T],
+    stride: usize,
+    src_rect: Rect,
+    dest_xy: (isize, isize),
+) -> Rect {
+    let full_rows = buf.len().checked_div(stride).unwrap_or(0);
+    let short_row = buf.len() - full_rows * stride;
+    let rows = full_rows + (short_row > 0) as usize;
+    let (src_x, dest_x, width) = clip_span(
+        src_rect.x,
+        src_rect.x.saturating_add(src_rect.width),
+        dest_xy.0,
+        stride,
+    );
+    let (src_y, dest_y, mut height) = clip_span(
+        src_rect.y,
+        src_rect.y.saturating_add(src_rect.height),
+        dest_xy.1,
+        rows,
+    );
+    // Only the bottom row of the copy can be in the short row, and it has to
+    // fit there on whichever side it is.
+    if height > 0 {
+        let overruns_short_row = |y: usize, x: usize| {
+            short_row > 0 && y + height - 1 == full_rows && x + width > short_row
+        };
+        if overruns_short_row(src_y, src_x) || overruns_short_row(dest_y, dest_x) {
+            height -= 1;
+        }
+    }
+    let clipped = Rect {
+        x: src_x,
+        y: src_y,
+        width,
+        height,
+    };
+    copy_rect_in_place(buf, stride, clipped, (dest_x, dest_y));
+    Rect {
+        x: dest_x,
+        y: dest_y,
+        width,
+        height,
+    }
+}
+
+/// Scrolls a band of rows in a row-major grid, like a terminal's scrolling
+/// region, and fills the rows that are exposed.
+///
+/// `buf` holds rows of `cols` elements each, and `rows` is the band to scroll.
+/// A positive `by` scrolls the contents up by that many rows, filling rows at
+/// the bottom of the band with `fill`, and a negative `by` scrolls down,
+/// filling at the top. If `by` is at least the height of the band, the whole
+/// band is filled. Rows outside the band are not touched.
+///
+/// # Panics
+///
+/// This function will panic if the end of `rows` is before the start, or if
+/// the band extends past the end of `buf`.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::scroll_region;
+/// let mut grid = *b"\
+///     aaa\
+///     bbb\
+///     ccc\
+///     ddd";
+///
+/// scroll_region(&mut grid, 3, 0..3, 1, b'.');
+///
+/// assert_eq!(&grid, b"\
+///     bbb\
+///     ccc\
+///     ...\
+///     ddd");
+/// ```
+pub fn scroll_region<T: Copy>(buf: &mut [T], cols: usize, rows: Range<usize>, by: isize, fill: T) {
+    assert!(rows.start <= rows.end, "rows end is before rows start");
+    let end = rows
+        .end
+        .checked_mul(cols)
+        .filter(|&end| end <= buf.len())
+        .expect("rows are out of bounds");
+    scroll_span(&mut buf[rows.start * cols..end], cols, by, fill);
+}
+
+/// Scrolls a span of columns within one row of a row-major grid, like a
+/// terminal's delete-character and insert-character operations, and fills the
+/// columns that are exposed.
+///
+/// `buf` holds rows of `cols` elements each, and `columns` is the span of row
+/// `row` to scroll. A positive `by` shifts the contents left by that many
+/// columns (deleting characters at the start of the span), filling at the
+/// right end with `fill`, and a negative `by` shifts right (inserting blanks),
+/// filling at the left end. If `by` is at least the width of the span, the
+/// whole span is filled.
+///
+/// # Panics
+///
+/// This function will panic if the end of `columns` is before the start, if
+/// `columns` extends past `cols`, or if the row extends past the end of `buf`.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::scroll_columns;
+/// let mut grid = *b"abcdef";
+///
+/// scroll_columns(&mut grid, 6, 0, 1..5, -2, b'_');
+///
+/// assert_eq!(&grid, b"a__bcf");
+/// ```
+pub fn scroll_columns<T: Copy>(
+    buf: &mut [T],
+    cols: usize,
+    row: usize,
+    columns: Range<usize>,
+    by: isize,
+    fill: T,
+) {
+    assert!(
+        columns.start <= columns.end,
+        "columns end is before columns start"
+    );
+    assert!(columns.end <= cols, "columns are out of bounds");
+    let row_end = row
+        .checked_add(1)
+        .and_then(|rows| rows.checked_mul(cols))
+        .filter(|&end| end <= buf.len())
+        .expect("row is out of bounds");
+    let row_start = row_end - cols;
+    scroll_span(
+        &mut buf[row_start + columns.start..row_start + columns.end],
+        1,
+        by,
+        fill,
+    );
+}
+
+/// Shifts `span` towards the front by `by` lines of `line_len` elements, or
+/// towards the back if `by` is negative, and fills the exposed lines.
+fn scroll_span<T: Copy>(span: &mut [T], line_len: usize, by: isize, fill: T) {
+    let lines = span.len().checked_div(line_len).unwrap_or(0);
+    let shift = cmp::min(by.unsigned_abs(), lines) * line_len;
+    let kept = span.len() - shift;
+    let exposed = if by > 0 {
+        copy_in_place(span, shift.., 0);
+        kept..span.len()
+    } else {
+        copy_in_place(span, ..kept, shift);
+        0..shift
+    };
+    for element in &mut span[exposed] {
+        *element = fill;
+    }
+}
+
+#[cfg(test)]
+fn naive_copy_rect<T: Copy>(buf: &mut [T], stride: usize, src: Rect, dest: (usize, usize)) {
+    let copy: std::vec::Vec<T> = (0..src.height)
+        .flat_map(|row| (0..src.width).map(move |col| (row, col)))
+        .map(|(row, col)| buf[(src.y + row) * stride + src.x + col])
+        .collect();
+    for row in 0..src.height {
+        for col in 0..src.width {
+            buf[(dest.1 + row) * stride + dest.0 + col] = copy[row * src.width + col];
+        }
+    }
+}
+
+#[test]
+fn test_rect_against_naive() {
+    let (stride, rows) = (4, 4);
+    let original: std::vec::Vec<u8> = (0..(stride * rows) as u8).collect();
+    for x in 0..=stride {
+        for y in 0..=rows {
+            for width in 0..=stride - x {
+                for height in 0..=rows - y {
+                    let rect = Rect {
+                        x,
+                        y,
+                        width,
+                        height,
+                    };
+                    for dest_x in 0..=stride - width {
+                        for dest_y in 0..=rows - height {
+                            let mut expected = original.clone();
+                            naive_copy_rect(&mut expected, stride, rect, (dest_x, dest_y));
+                            let mut buf = original.clone();
+                            copy_rect_in_place(&mut buf, stride, rect, (dest_x, dest_y));
+                            assert_eq!(buf, expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rect_short_last_row() {
+    // The last row only needs to be as long as the rectangle.
+    let mut buf = *b"ab..cd.";
+    let rect = Rect {
+        x: 0,
+        y: 0,
+        width: 2,
+        height: 1,
+    };
+    copy_rect_in_place(&mut buf, 4, rect, (1, 1));
+    assert_eq!(&buf, b"ab..cab");
+}
+
+#[test]
+fn test_rect_clipped_short_last_row() {
+    // The same copy as `test_rect_short_last_row` isn't clipped.
+    let mut buf = *b"ab..cd.";
+    let rect = Rect {
+        x: 0,
+        y: 0,
+        width: 2,
+        height: 1,
+    };
+    let written = copy_rect_in_place_clipped(&mut buf, 4, rect, (1, 1));
+    assert_eq!(written, Rect { x: 1, y: 1, ..rect });
+    assert_eq!(&buf, b"ab..cab");
+    // One column further right doesn't fit in the short row, so that row of
+    // the copy is dropped.
+    let mut buf = *b"ab..cd.";
+    let written = copy_rect_in_place_clipped(&mut buf, 4, rect, (2, 1));
+    assert_eq!(written.width * written.height, 0);
+    assert_eq!(&buf, b"ab..cd.");
+    // Copying out of the short row works too.
+    let mut buf = *b"ab..cd.";
+    let rect = Rect {
+        x: 0,
+        y: 0,
+        width: 3,
+        height: 2,
+    };
+    let written = copy_rect_in_place_clipped(&mut buf, 4, rect, (1, -1));
+    assert_eq!(
+        written,
+        Rect {
+            x: 1,
+            y: 0,
+            width: 3,
+            height: 1
+        }
+    );
+    assert_eq!(&buf, b"acd.cd.");
+}
+
+#[test]
+#[should_panic(expected = "dest rect is out of bounds")]
+fn test_rect_past_right_edge() {
+    let mut buf = [0u8; 16];
+    let rect = Rect {
+        x: 0,
+        y: 0,
+        width: 2,
+        height: 2,
+    };
+    copy_rect_in_place(&mut buf, 4, rect, (3, 0));
+}
+
+#[test]
+#[should_panic(expected = "src rect is out of bounds")]
+fn test_rect_past_end() {
+    let mut buf = [0u8; 15];
+    let rect = Rect {
+        x: 2,
+        y: 2,
+        width: 2,
+        height: 2,
+    };
+    copy_rect_in_place(&mut buf, 4, rect, (0, 0));
+}
+
+#[test]
+fn test_rect_clipped_against_naive() {
+    let (stride, rows) = (4, 3);
+    let original: std::vec::Vec<u8> = (0..(stride * rows) as u8).collect();
+    for x in 0..6 {
+        for y in 0..5 {
+            for width in 0..6 {
+                for height in 0..5 {
+                    let rect = Rect {
+                        x,
+                        y,
+                        width,
+                        height,
+                    };
+                    for dest_x in -6..6isize {
+                        for dest_y in -5..5isize {
+                            // Copy element by element through a snapshot,
+                            // skipping anything outside the grid.
+                            let mut expected = original.clone();
+                            let mut written = 0;
+                            for row in 0..height {
+                                for col in 0..width {
+                                    let (sx, sy) = (x + col, y + row);
+                                    let dx = dest_x + col as isize;
+                                    let dy = dest_y + row as isize;
+                                    if sx < stride
+                                        && sy < rows
+                                        && (0..stride as isize).contains(&dx)
+                                        && (0..rows as isize).contains(&dy)
+                                    {
+                                        let dest = dy * stride as isize + dx;
+                                        expected[dest as usize] = original[sy * stride + sx];
+                                        written += 1;
+                                    }
+                                }
+                            }
+                            let mut buf = original.clone();
+                            let result = copy_rect_in_place_clipped(
+                                &mut buf,
+                                stride,
+                                rect,
+                                (dest_x, dest_y),
+                            );
+                            assert_eq!(buf, expected);
+                            assert_eq!(result.width * result.height, written);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_scroll_region_against_naive() {
+    let (cols, rows) = (3, 5);
+    let original: std::vec::Vec<u8> = (0..(cols * rows) as u8).collect();
+    for top in 0..=rows {
+        for bottom in top..=rows {
+            for by in -7..=7isize {
+                let mut expected = original.clone();
+                for row in top..bottom {
+                    let from = row as isize + by;
+                    for col in 0..cols {
+                        expected[row * cols + col] =
+                            if (top as isize..bottom as isize).contains(&from) {
+                                original[from as usize * cols + col]
+                            } else {
+                                0xff
+                            };
+                    }
+                }
+                let mut buf = original.clone();
+                scroll_region(&mut buf, cols, top..bottom, by, 0xff);
+                assert_eq!(buf, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_scroll_columns() {
+    let mut grid = *b"abcdefghijkl";
+    scroll_columns(&mut grid, 4, 1, 0..4, 1, b' ');
+    assert_eq!(&grid, b"abcdfgh ijkl");
+    scroll_columns(&mut grid, 4, 2, 1..3, -1, b' ');
+    assert_eq!(&grid, b"abcdfgh i jl");
+    scroll_columns(&mut grid, 4, 0, 1..4, isize::MIN, b'_');
+    assert_eq!(&grid, b"a___fgh i jl");
+    scroll_columns(&mut grid, 4, 0, 2..2, 1, b'_');
+    assert_eq!(&grid, b"a___fgh i jl");
+}
+
+#[test]
+#[should_panic(expected = "rows are out of bounds")]
+fn test_scroll_region_out_of_bounds() {
+    let mut grid = [0u8; 11];
+    scroll_region(&mut grid, 3, 1..4, 1, 0);
+}
+
+#[test]
+#[should_panic(expected = "row is out of bounds")]
+fn test_scroll_columns_out_of_bounds() {
+    let mut grid = [0u8; 11];
+    scroll_columns(&mut grid, 3, 3, 0..1, 1, 0);
+}
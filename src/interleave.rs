@@ -0,0 +1,102 @@
+use super::transpose_in_place;
+
+/// Converts planar channel data to interleaved, in place.
+///
+/// The slice holds `channels` equal-length planes one after another, like
+/// `LLLLRRRR`. Afterwards it holds one frame after another, each with one
+/// element from every channel, like `LRLRLRLR`. This is a transpose of a
+/// `channels` by `len / channels` matrix, done with [`transpose_in_place`]
+/// without heap allocation.
+///
+/// # Panics
+///
+/// This function will panic if `channels` is zero, or if the length of the
+/// slice is not a multiple of `channels`.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::interleave_in_place;
+/// let mut samples = *b"LLLLRRRR";
+///
+/// interleave_in_place(&mut samples, 2);
+///
+/// assert_eq!(&samples, b"LRLRLRLR");
+/// ```
+///
+/// [`transpose_in_place`]: fn.transpose_in_place.html
+pub fn interleave_in_place<T: Copy>(slice: &mut [T], channels: usize) {
+    let frames = frames(slice, channels);
+    transpose_in_place(slice, channels, frames);
+}
+
+/// Converts interleaved channel data to planar, in place. This is the inverse
+/// of [`interleave_in_place`].
+///
+/// # Panics
+///
+/// This function will panic if `channels` is zero, or if the length of the
+/// slice is not a multiple of `channels`.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::deinterleave_in_place;
+/// let mut vertices = *b"xyzxyzxyz";
+///
+/// deinterleave_in_place(&mut vertices, 3);
+///
+/// assert_eq!(&vertices, b"xxxyyyzzz");
+/// ```
+///
+/// [`interleave_in_place`]: fn.interleave_in_place.html
+pub fn deinterleave_in_place<T: Copy>(slice: &mut [T], channels: usize) {
+    let frames = frames(slice, channels);
+    transpose_in_place(slice, frames, channels);
+}
+
+fn frames<T>(slice: &[T], channels: usize) -> usize {
+    assert!(channels != 0, "channels is zero");
+    assert!(
+        slice.len() % channels == 0,
+        "slice length is not a multiple of channels"
+    );
+    slice.len() / channels
+}
+
+#[cfg(test)]
+fn naive_interleave(planar: &[u32], channels: usize) -> std::vec::Vec<u32> {
+    let frames = planar.len() / channels;
+    (0..planar.len())
+        .map(|i| planar[(i % channels) * frames + i / channels])
+        .collect()
+}
+
+#[test]
+fn test_interleave_against_naive() {
+    for channels in 1..=8 {
+        for frames in 0..=20 {
+            let planar: std::vec::Vec<u32> = (0..(channels * frames) as u32).collect();
+            let expected = naive_interleave(&planar, channels);
+            let mut buf = planar.clone();
+            interleave_in_place(&mut buf, channels);
+            assert_eq!(buf, expected, "{} channels, {} frames", channels, frames);
+            deinterleave_in_place(&mut buf, channels);
+            assert_eq!(buf, planar, "{} channels, {} frames", channels, frames);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "slice length is not a multiple of channels")]
+fn test_interleave_bad_length() {
+    let mut samples = [0u8; 7];
+    interleave_in_place(&mut samples, 2);
+}
+
+#[test]
+#[should_panic(expected = "channels is zero")]
+fn test_deinterleave_zero_channels() {
+    let mut samples = [0u8; 0];
+    deinterleave_in_place(&mut samples, 0);
+}
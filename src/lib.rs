@@ -2,8 +2,9 @@
 //! [Docs](https://docs.rs/copy_in_place) —
 //! [Crate](https://crates.io/crates/copy_in_place)
 //!
-//! This crate provides a single function, a safe wrapper around [`ptr::copy`]
-//! for efficient copying within slices.
+//! This crate provides a safe wrapper around [`ptr::copy`] for efficient
+//! copying within slices, along with [`try_copy_in_place`], a variant that
+//! returns a [`CopyError`] instead of panicking.
 //!
 //! **DEPRECATED:** As of Rust 1.37, the standard library provides the equivalent
 //! [`copy_within`](https://doc.rust-lang.org/std/primitive.slice.html#method.copy_within)
@@ -22,61 +23,1194 @@
 //! assert_eq!(&bytes, b"Hello, Wello!");
 //! ```
 //!
+//! # Features
+//!
+//! The crate is `#![no_std]` by default. The `alloc` feature implements
+//! [`CopyInPlace`] for `Vec<T>` and `Box<[T]>`, and adds [`GapBuffer`] and
+//! [`copy_in_place_vec_deque`]. The `std` feature (which implies `alloc`)
+//! implements `std::error::Error` for [`CopyError`].
+//!
+//! # Minimum Rust version
+//!
+//! This crate requires Rust 1.83 or later, because
+//! [`copy_in_place_const`] and [`copy_in_place_const_range`] are `const fn`s
+//! that write through `&mut`. Version 0.2.2 and earlier built on much older
+//! compilers.
+//!
 //! [`ptr::copy`]: https://doc.rust-lang.org/std/ptr/fn.copy.html
+//! [`try_copy_in_place`]: fn.try_copy_in_place.html
+//! [`CopyError`]: enum.CopyError.html
+//! [`CopyInPlace`]: trait.CopyInPlace.html
+//! [`GapBuffer`]: struct.GapBuffer.html
+//! [`copy_in_place_vec_deque`]: fn.copy_in_place_vec_deque.html
+//! [`copy_in_place_const`]: fn.copy_in_place_const.html
+//! [`copy_in_place_const_range`]: fn.copy_in_place_const_range.html
 //! [PR #53652]: https://github.com/rust-lang/rust/pull/53652
 
-#![no_std]
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
+use core::cell::Cell;
+use core::fmt;
+use core::ops::Bound;
+use core::ops::Range;
+use core::ops::RangeBounds;
+
+mod atomic;
+mod bits;
+mod block;
+#[cfg(feature = "alloc")]
+mod gap_buffer;
+mod grid;
+mod interleave;
+mod ring;
+mod strided;
+mod transpose;
+
+pub use atomic::{copy_in_place_atomic, AtomicInteger};
+pub use bits::{copy_bits_in_place, BitOrder};
+pub use block::copy_block_in_place;
+#[cfg(feature = "alloc")]
+pub use gap_buffer::GapBuffer;
+pub use grid::{
+    copy_rect_in_place, copy_rect_in_place_clipped, scroll_columns, scroll_region, Rect,
+};
+pub use interleave::{deinterleave_in_place, interleave_in_place};
+pub use ring::copy_in_place_ring;
+#[cfg(feature = "alloc")]
+pub use ring::copy_in_place_vec_deque;
+pub use strided::{copy_strided_in_place, try_copy_strided_in_place};
+pub use transpose::transpose_in_place;
+
+/// The error returned by the `try_` functions in this crate when the requested
+/// copy is invalid.
+///
+/// Each variant carries the indices that caused it. Which variants a function
+/// can return depends on the function:
+///
+/// - [`try_copy_in_place`] and [`CopyInPlace::try_copy_in_place`] return
+///   `RangeOverflow`, `SrcEndBeforeStart`, `SrcOutOfBounds` and
+///   `DestOutOfBounds`, which correspond to the panics documented on
+///   [`copy_in_place`].
+/// - [`try_swap_ranges_in_place`] returns those four, reporting its first range
+///   as `src` and its second as `dest`, and also `Overlap` when the two ranges
+///   overlap.
+/// - [`try_insert_gap_in_place`] returns `LenOutOfBounds`, `PosOutOfBounds`
+///   and `GapOutOfBounds`, which correspond to the panics documented on
+///   [`insert_gap_in_place`].
+/// - [`try_copy_many`] returns the same variants as [`try_copy_in_place`],
+///   wrapped in a [`CopyManyError`] with the index of the invalid op.
+/// - [`try_copy_strided_in_place`] returns `StridedOverflow`,
+///   `StridedSrcOutOfBounds`, `StridedDestOutOfBounds` and `NoSinglePassOrder`.
+///
+/// [`try_copy_in_place`]: fn.try_copy_in_place.html
+/// [`CopyInPlace::try_copy_in_place`]: trait.CopyInPlace.html#tymethod.try_copy_in_place
+/// [`copy_in_place`]: fn.copy_in_place.html
+/// [`try_swap_ranges_in_place`]: fn.try_swap_ranges_in_place.html
+/// [`try_insert_gap_in_place`]: fn.try_insert_gap_in_place.html
+/// [`insert_gap_in_place`]: fn.insert_gap_in_place.html
+/// [`try_copy_many`]: fn.try_copy_many.html
+/// [`CopyManyError`]: struct.CopyManyError.html
+/// [`try_copy_strided_in_place`]: fn.try_copy_strided_in_place.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CopyError {
+    /// A range bound was `Included(usize::MAX)` at the end or
+    /// `Excluded(usize::MAX)` at the start, so the range can't be represented.
+    RangeOverflow,
+    /// The end of `src` is before its start.
+    SrcEndBeforeStart { start: usize, end: usize },
+    /// The end of `src` is past the end of the slice.
+    SrcOutOfBounds { end: usize, len: usize },
+    /// The destination range `dest..dest + count` is past the end of the slice.
+    DestOutOfBounds {
+        dest: usize,
+        count: usize,
+        len: usize,
+    },
+    /// The source and destination ranges overlap, for operations that require
+    /// them to be disjoint.
+    Overlap {
+        src_start: usize,
+        dest: usize,
+        count: usize,
+    },
+    /// The live prefix length `len` is past the end of the slice, whose length
+    /// is `capacity`.
+    LenOutOfBounds { len: usize, capacity: usize },
+    /// The position `pos` is past the end of the live prefix of length `len`.
+    PosOutOfBounds { pos: usize, len: usize },
+    /// Growing the live prefix from `len` to `len + n` elements would go past
+    /// the end of the slice, whose length is `capacity`, or overflow `usize`.
+    GapOutOfBounds {
+        len: usize,
+        n: usize,
+        capacity: usize,
+    },
+    /// The index of the last element of a strided copy,
+    /// `start + (count - 1) * stride`, overflows `usize`.
+    StridedOverflow {
+        start: usize,
+        stride: usize,
+        count: usize,
+    },
+    /// The last element of a strided source, at index `last`, is past the end
+    /// of the slice.
+    StridedSrcOutOfBounds { last: usize, len: usize },
+    /// The last element of a strided destination, at index `last`, is past the
+    /// end of the slice.
+    StridedDestOutOfBounds { last: usize, len: usize },
+    /// A strided copy would overwrite some source element before reading it,
+    /// whether it went front to back or back to front.
+    NoSinglePassOrder,
+}
+
+impl fmt::Display for CopyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CopyError::RangeOverflow => write!(f, "range bound overflows usize"),
+            CopyError::SrcEndBeforeStart { start, end } => write!(
+                f,
+                "src end is before src start (start: {}, end: {})",
+                start, end
+            ),
+            CopyError::SrcOutOfBounds { end, len } => {
+                write!(f, "src is out of bounds (end: {}, len: {})", end, len)
+            }
+            CopyError::DestOutOfBounds { dest, count, len } => write!(
+                f,
+                "dest is out of bounds (dest: {}, count: {}, len: {})",
+                dest, count, len
+            ),
+            CopyError::Overlap {
+                src_start,
+                dest,
+                count,
+            } => write!(
+                f,
+                "src and dest overlap (src start: {}, dest: {}, count: {})",
+                src_start, dest, count
+            ),
+            CopyError::LenOutOfBounds { len, capacity } => write!(
+                f,
+                "len is out of bounds (len: {}, capacity: {})",
+                len, capacity
+            ),
+            CopyError::PosOutOfBounds { pos, len } => {
+                write!(f, "pos is out of bounds (pos: {}, len: {})", pos, len)
+            }
+            CopyError::GapOutOfBounds { len, n, capacity } => write!(
+                f,
+                "len + n is out of bounds (len: {}, n: {}, capacity: {})",
+                len, n, capacity
+            ),
+            CopyError::StridedOverflow {
+                start,
+                stride,
+                count,
+            } => write!(
+                f,
+                "strided last index overflows usize (start: {}, stride: {}, count: {})",
+                start, stride, count
+            ),
+            CopyError::StridedSrcOutOfBounds { last, len } => write!(
+                f,
+                "strided src is out of bounds (last: {}, len: {})",
+                last, len
+            ),
+            CopyError::StridedDestOutOfBounds { last, len } => write!(
+                f,
+                "strided dest is out of bounds (last: {}, len: {})",
+                last, len
+            ),
+            CopyError::NoSinglePassOrder => {
+                write!(f, "strided copy has no single-pass order")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CopyError {}
+
+/// The error returned by [`try_copy_many`] when one of the copies is out of
+/// bounds.
+///
+/// [`try_copy_many`]: fn.try_copy_many.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CopyManyError {
+    /// The index of the first invalid operation.
+    pub index: usize,
+    /// What was wrong with it.
+    pub error: CopyError,
+}
+
+impl fmt::Display for CopyManyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "copy {}: {}", self.index, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CopyManyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Resolves `range` against a slice of length `len`, returning `(start, end)`.
+fn resolve_range<R: RangeBounds<usize>>(
+    range: &R,
+    len: usize,
+) -> Result<(usize, usize), CopyError> {
+    let start = match range.start_bound() {
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n.checked_add(1).ok_or(CopyError::RangeOverflow)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&n) => n.checked_add(1).ok_or(CopyError::RangeOverflow)?,
+        Bound::Excluded(&n) => n,
+        Bound::Unbounded => len,
+    };
+    if start > end {
+        return Err(CopyError::SrcEndBeforeStart { start, end });
+    }
+    if end > len {
+        return Err(CopyError::SrcOutOfBounds { end, len });
+    }
+    Ok((start, end))
+}
+
+/// Resolves `range` like `resolve_range`, but panics with a message about
+/// `range` rather than `src`, for functions whose range argument isn't the
+/// source of a copy.
+fn resolve_range_or_panic<R: RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+    match resolve_range(range, len) {
+        Ok(resolved) => resolved,
+        Err(CopyError::SrcEndBeforeStart { start, end }) => panic!(
+            "range end is before range start (start: {}, end: {})",
+            start, end
+        ),
+        Err(CopyError::SrcOutOfBounds { end, len }) => {
+            panic!("range is out of bounds (end: {}, len: {})", end, len)
+        }
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Checks that `count` elements starting at `dest` fit in a slice of length
+/// `len`.
+fn check_dest(dest: usize, count: usize, len: usize) -> Result<(), CopyError> {
+    if dest > len - count {
+        return Err(CopyError::DestOutOfBounds { dest, count, len });
+    }
+    Ok(())
+}
+
+/// Resolves and checks a copy of `src` to `dest` within a slice of length
+/// `len`, returning `(src_start, count)`.
+fn resolve_copy<R: RangeBounds<usize>>(
+    src: &R,
+    dest: usize,
+    len: usize,
+) -> Result<(usize, usize), CopyError> {
+    let (src_start, src_end) = resolve_range(src, len)?;
+    let count = src_end - src_start;
+    check_dest(dest, count, len)?;
+    Ok((src_start, count))
+}
+
+/// Copies elements from one part of a slice to another part of the same
+/// slice, using a memmove.
+///
+/// `src` is the range within the slice to copy from. `dest` is the starting
+/// index of the range within the slice to copy to, which will have the same
+/// length as `src`. The two ranges may overlap. The ends of the two ranges must
+/// be less than or equal to `slice.len()`.
+///
+/// # Panics
+///
+/// This function will panic if either range exceeds the end of the slice, or if
+/// the end of `src` is before the start.
+///
+/// # Examples
+///
+/// Copying four bytes within a slice:
+///
+/// ```
+/// # use copy_in_place::copy_in_place;
+/// let mut bytes = *b"Hello, World!";
+///
+/// copy_in_place(&mut bytes, 1..5, 8);
+///
+/// assert_eq!(&bytes, b"Hello, Wello!");
+/// ```
+pub fn copy_in_place<T: Copy, R: RangeBounds<usize>>(slice: &mut [T], src: R, dest: usize) {
+    CopyInPlace::copy_in_place(slice, src, dest);
+}
+
+/// Like [`copy_in_place`], but returns a [`CopyError`] instead of panicking if
+/// either range is out of bounds. The slice is not modified on error.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::{try_copy_in_place, CopyError};
+/// let mut bytes = *b"Hello, World!";
+///
+/// assert_eq!(try_copy_in_place(&mut bytes, 1..5, 8), Ok(()));
+/// assert_eq!(&bytes, b"Hello, Wello!");
+///
+/// assert_eq!(
+///     try_copy_in_place(&mut bytes, 1..5, 10),
+///     Err(CopyError::DestOutOfBounds { dest: 10, count: 4, len: 13 }),
+/// );
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+/// [`CopyError`]: enum.CopyError.html
+pub fn try_copy_in_place<T: Copy, R: RangeBounds<usize>>(
+    slice: &mut [T],
+    src: R,
+    dest: usize,
+) -> Result<(), CopyError> {
+    CopyInPlace::try_copy_in_place(slice, src, dest)
+}
+
+/// Containers that support [`copy_in_place`] as a method.
+///
+/// This is implemented for slices and arrays, and with the `alloc` feature for
+/// `Vec<T>` and `Box<[T]>`. Other containers can implement it by forwarding
+/// [`try_copy_in_place`] to their underlying slice.
+///
+/// # Examples
+///
+/// ```
+/// use copy_in_place::CopyInPlace;
+///
+/// let mut bytes = *b"Hello, World!";
+/// bytes.copy_in_place(1..5, 8);
+/// assert_eq!(&bytes, b"Hello, Wello!");
+/// ```
+///
+/// Implementing the trait for a custom buffer:
+///
+/// ```
+/// use copy_in_place::{CopyError, CopyInPlace};
+/// use std::ops::RangeBounds;
+///
+/// struct Buffer {
+///     bytes: [u8; 16],
+///     len: usize,
+/// }
+///
+/// impl CopyInPlace for Buffer {
+///     fn try_copy_in_place<R: RangeBounds<usize>>(
+///         &mut self,
+///         src: R,
+///         dest: usize,
+///     ) -> Result<(), CopyError> {
+///         self.bytes[..self.len].try_copy_in_place(src, dest)
+///     }
+/// }
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+/// [`try_copy_in_place`]: #tymethod.try_copy_in_place
+pub trait CopyInPlace {
+    /// Like [`copy_in_place`], but returns a [`CopyError`] instead of
+    /// panicking if either range is out of bounds. The container is not
+    /// modified on error.
+    ///
+    /// [`copy_in_place`]: fn.copy_in_place.html
+    /// [`CopyError`]: enum.CopyError.html
+    fn try_copy_in_place<R: RangeBounds<usize>>(
+        &mut self,
+        src: R,
+        dest: usize,
+    ) -> Result<(), CopyError>;
+
+    /// Copies elements from one part of the container to another part of the
+    /// same container, with the same semantics and panics as the
+    /// [`copy_in_place`] function.
+    ///
+    /// [`copy_in_place`]: fn.copy_in_place.html
+    fn copy_in_place<R: RangeBounds<usize>>(&mut self, src: R, dest: usize) {
+        if let Err(e) = self.try_copy_in_place(src, dest) {
+            panic!("{}", e);
+        }
+    }
+}
+
+impl<T: Copy> CopyInPlace for [T] {
+    fn try_copy_in_place<R: RangeBounds<usize>>(
+        &mut self,
+        src: R,
+        dest: usize,
+    ) -> Result<(), CopyError> {
+        let (src_start, count) = resolve_copy(&src, dest, self.len())?;
+        // Derive both `src_ptr` and `dest_ptr` from the same loan
+        unsafe { memmove(self.as_mut_ptr(), src_start, count, dest) };
+        Ok(())
+    }
+}
+
+/// Copies `count` elements from `ptr + src_start` to `ptr + dest`, with a
+/// memmove. Both `src_ptr` and `dest_ptr` are derived from `ptr`, so callers
+/// should derive it from a single loan of the whole slice.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads and writes of `src_start + count` and
+/// `dest + count` elements.
+const unsafe fn memmove<T>(ptr: *mut T, src_start: usize, count: usize, dest: usize) {
+    let src_ptr = ptr.add(src_start);
+    let dest_ptr = ptr.add(dest);
+    core::ptr::copy(src_ptr, dest_ptr, count);
+}
+
+impl<T: Copy, const N: usize> CopyInPlace for [T; N] {
+    fn try_copy_in_place<R: RangeBounds<usize>>(
+        &mut self,
+        src: R,
+        dest: usize,
+    ) -> Result<(), CopyError> {
+        self[..].try_copy_in_place(src, dest)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Copy> CopyInPlace for alloc::vec::Vec<T> {
+    fn try_copy_in_place<R: RangeBounds<usize>>(
+        &mut self,
+        src: R,
+        dest: usize,
+    ) -> Result<(), CopyError> {
+        self[..].try_copy_in_place(src, dest)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Copy> CopyInPlace for alloc::boxed::Box<[T]> {
+    fn try_copy_in_place<R: RangeBounds<usize>>(
+        &mut self,
+        src: R,
+        dest: usize,
+    ) -> Result<(), CopyError> {
+        self[..].try_copy_in_place(src, dest)
+    }
+}
+
+/// Copies `length` elements to `pos` from `distance` elements behind it, one
+/// element at a time from front to back, like an LZ77 back-reference.
+///
+/// Unlike [`copy_in_place`], when the source and destination overlap
+/// (`distance < length`) the copy reads elements that it has already written,
+/// so the `distance` elements before `pos` are repeated as a pattern. A
+/// `distance` of 1 produces a run of a single element.
+///
+/// Rather than copying one element at a time, this does a single memmove when
+/// `distance >= length`, and otherwise copies chunks that double in size as
+/// the pattern is repeated.
+///
+/// # Panics
+///
+/// This function will panic if `distance` is zero, if `distance` is greater
+/// than `pos`, or if `pos + length` exceeds the end of the slice.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_backref;
+/// let mut bytes = *b"abc.......";
+///
+/// copy_backref(&mut bytes, 3, 3, 7);
+///
+/// assert_eq!(&bytes, b"abcabcabca");
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn copy_backref<T: Copy>(slice: &mut [T], pos: usize, distance: usize, length: usize) {
+    assert!(distance != 0, "distance is zero");
+    assert!(distance <= pos, "distance is out of bounds");
+    assert!(pos <= slice.len(), "pos is out of bounds");
+    assert!(length <= slice.len() - pos, "length is out of bounds");
+    let src_start = pos - distance;
+    unsafe {
+        // Derive both `src_ptr` and `dest_ptr` from the same loan
+        let ptr = slice.as_mut_ptr();
+        let src_ptr = ptr.add(src_start);
+        if distance >= length {
+            core::ptr::copy(src_ptr, ptr.add(pos), length);
+            return;
+        }
+        // Everything from `src_start` up to `pos + copied` repeats with period
+        // `distance`, and `copied` is always a multiple of `distance` until the
+        // last chunk, so copying from `src_start` continues the pattern. Each
+        // chunk ends where the next one starts, so they never overlap.
+        let mut copied = 0;
+        while copied < length {
+            let chunk = core::cmp::min(copied + distance, length - copied);
+            core::ptr::copy_nonoverlapping(src_ptr, ptr.add(pos + copied), chunk);
+            copied += chunk;
+        }
+    }
+}
+
+/// Swaps two non-overlapping ranges of the same slice.
+///
+/// `a` is the first range. `b_start` is the starting index of the second
+/// range, which will have the same length as `a`. The ends of the two ranges
+/// must be less than or equal to `slice.len()`.
+///
+/// # Panics
+///
+/// This function will panic if either range exceeds the end of the slice, if
+/// the end of `a` is before the start, or if the two ranges overlap.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::swap_ranges_in_place;
+/// let mut bytes = *b"Hello, World!";
+///
+/// swap_ranges_in_place(&mut bytes, 0..5, 7);
+///
+/// assert_eq!(&bytes, b"World, Hello!");
+/// ```
+pub fn swap_ranges_in_place<T, R: RangeBounds<usize>>(slice: &mut [T], a: R, b_start: usize) {
+    if let Err(e) = try_swap_ranges_in_place(slice, a, b_start) {
+        panic!("{}", e);
+    }
+}
+
+/// Like [`swap_ranges_in_place`], but returns a [`CopyError`] instead of
+/// panicking. The slice is not modified on error.
+///
+/// [`swap_ranges_in_place`]: fn.swap_ranges_in_place.html
+/// [`CopyError`]: enum.CopyError.html
+pub fn try_swap_ranges_in_place<T, R: RangeBounds<usize>>(
+    slice: &mut [T],
+    a: R,
+    b_start: usize,
+) -> Result<(), CopyError> {
+    let (a_start, count) = resolve_copy(&a, b_start, slice.len())?;
+    if a_start < b_start + count && b_start < a_start + count {
+        return Err(CopyError::Overlap {
+            src_start: a_start,
+            dest: b_start,
+            count,
+        });
+    }
+    unsafe {
+        // Derive both `a_ptr` and `b_ptr` from the same loan
+        let ptr = slice.as_mut_ptr();
+        let a_ptr = ptr.add(a_start);
+        let b_ptr = ptr.add(b_start);
+        core::ptr::swap_nonoverlapping(a_ptr, b_ptr, count);
+    }
+    Ok(())
+}
+
+/// Moves a range of elements to another position in the same slice, shifting
+/// the elements in between to fill the gap.
+///
+/// `src` is the range of elements to move. `dest` is the index where the first
+/// moved element ends up, so the range `dest..dest + src.len()` holds the moved
+/// elements afterwards, in their original order. Unlike [`copy_in_place`], no
+/// element is duplicated or dropped, so `T` doesn't need to be `Copy`. This is
+/// implemented by rotating the part of the slice spanned by the two ranges.
+///
+/// # Panics
+///
+/// This function will panic if either range exceeds the end of the slice, or if
+/// the end of `src` is before the start.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::move_range_to;
+/// let mut items = ["a", "b", "c", "d", "e", "f"];
+///
+/// move_range_to(&mut items, 1..3, 3);
+///
+/// assert_eq!(items, ["a", "d", "e", "b", "c", "f"]);
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn move_range_to<T, R: RangeBounds<usize>>(slice: &mut [T], src: R, dest: usize) {
+    let (src_start, count) = match resolve_copy(&src, dest, slice.len()) {
+        Ok(resolved) => resolved,
+        Err(e) => panic!("{}", e),
+    };
+    if dest < src_start {
+        slice[dest..src_start + count].rotate_right(count);
+    } else if dest > src_start {
+        slice[src_start..dest + count].rotate_left(count);
+    }
+}
+
+/// Clones elements from one part of a slice to another part of the same slice.
+///
+/// This is like [`copy_in_place`] for types that are `Clone` but not `Copy`,
+/// and it takes the same arguments. The result is the same as cloning all of
+/// `src` before writing any of it, even when the two ranges overlap. Existing
+/// destination elements are updated with [`Clone::clone_from`], so they can
+/// reuse their allocations.
+///
+/// If a call to `clone_from` panics, the elements written before it keep their
+/// new values, the remaining elements keep their old values, and the slice is
+/// still valid.
+///
+/// # Panics
+///
+/// This function will panic if either range exceeds the end of the slice, or if
+/// the end of `src` is before the start. It will also propagate any panic from
+/// `clone_from`.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::clone_in_place;
+/// let mut words = ["a", "b", "c", "d", "e"].map(String::from);
+///
+/// clone_in_place(&mut words, 0..3, 2);
+///
+/// assert_eq!(words, ["a", "b", "a", "b", "c"]);
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+/// [`Clone::clone_from`]: https://doc.rust-lang.org/std/clone/trait.Clone.html#method.clone_from
+pub fn clone_in_place<T: Clone, R: RangeBounds<usize>>(slice: &mut [T], src: R, dest: usize) {
+    let (src_start, count) = match resolve_copy(&src, dest, slice.len()) {
+        Ok(resolved) => resolved,
+        Err(e) => panic!("{}", e),
+    };
+    for_each_memmove_order(src_start, dest, count, |i| {
+        clone_element(slice, src_start + i, dest + i);
+    });
+}
+
+/// Calls `f(i)` for each `i` in `0..count`, in the order that a memmove would
+/// copy element `src_start + i` to `dest + i`: front to back when copying
+/// towards the front, and back to front otherwise, so that no source element
+/// is overwritten before it's read. Nothing is called when `dest == src_start`.
+fn for_each_memmove_order<F: FnMut(usize)>(src_start: usize, dest: usize, count: usize, mut f: F) {
+    if dest < src_start {
+        for i in 0..count {
+            f(i);
+        }
+    } else if dest > src_start {
+        for i in (0..count).rev() {
+            f(i);
+        }
+    }
+}
+
+fn clone_element<T: Clone>(slice: &mut [T], src: usize, dest: usize) {
+    if src < dest {
+        let (front, back) = slice.split_at_mut(dest);
+        back[0].clone_from(&front[src]);
+    } else {
+        let (front, back) = slice.split_at_mut(src);
+        front[dest].clone_from(&back[0]);
+    }
+}
+
+/// Copies elements within a shared slice of [`Cell`]s, like [`copy_in_place`].
+///
+/// This is useful when other code holds references into the same buffer, for
+/// example views created with [`Cell::as_slice_of_cells`]. The arguments and
+/// panics are the same as [`copy_in_place`].
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_in_place_cells;
+/// use std::cell::Cell;
+///
+/// let mut bytes = *b"Hello, World!";
+/// let cells = Cell::from_mut(&mut bytes[..]).as_slice_of_cells();
+/// let world = &cells[7..12];
+///
+/// copy_in_place_cells(cells, 1..5, 8);
+///
+/// assert_eq!(world[1].get(), b'e');
+/// assert_eq!(&bytes, b"Hello, Wello!");
+/// ```
+///
+/// [`Cell`]: https://doc.rust-lang.org/core/cell/struct.Cell.html
+/// [`Cell::as_slice_of_cells`]: https://doc.rust-lang.org/core/cell/struct.Cell.html#method.as_slice_of_cells
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn copy_in_place_cells<T: Copy, R: RangeBounds<usize>>(slice: &[Cell<T>], src: R, dest: usize) {
+    let (src_start, count) = match resolve_copy(&src, dest, slice.len()) {
+        Ok(resolved) => resolved,
+        Err(e) => panic!("{}", e),
+    };
+    // `Cell<T>` has the same layout as `T`, and it allows writes through a
+    // shared reference. `Cell` is not `Sync`, so no other thread can be
+    // accessing the slice. Derive both `src_ptr` and `dest_ptr` from the
+    // same loan.
+    unsafe { memmove(slice.as_ptr() as *mut T, src_start, count, dest) };
+}
+
+/// Copies elements within a slice one at a time with volatile reads and
+/// writes, like [`copy_in_place`].
+///
+/// Every element in `src` is read exactly once with [`ptr::read_volatile`] and
+/// every element in the destination is written exactly once with
+/// [`ptr::write_volatile`], so the compiler can't merge, reorder or drop those
+/// accesses. This is useful for buffers in memory-mapped I/O regions. The
+/// result is correct when the two ranges overlap. The arguments and panics are
+/// the same as [`copy_in_place`].
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_in_place_volatile;
+/// let mut words = [1u32, 2, 3, 4, 5];
+///
+/// copy_in_place_volatile(&mut words, 0..3, 2);
+///
+/// assert_eq!(words, [1, 2, 1, 2, 3]);
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+/// [`ptr::read_volatile`]: https://doc.rust-lang.org/core/ptr/fn.read_volatile.html
+/// [`ptr::write_volatile`]: https://doc.rust-lang.org/core/ptr/fn.write_volatile.html
+pub fn copy_in_place_volatile<T: Copy, R: RangeBounds<usize>>(
+    slice: &mut [T],
+    src: R,
+    dest: usize,
+) {
+    let (src_start, count) = match resolve_copy(&src, dest, slice.len()) {
+        Ok(resolved) => resolved,
+        Err(e) => panic!("{}", e),
+    };
+    // Derive both `src_ptr` and `dest_ptr` from the same loan
+    let ptr = slice.as_mut_ptr();
+    for_each_memmove_order(src_start, dest, count, |i| unsafe {
+        let src_ptr = ptr.add(src_start + i);
+        let dest_ptr = ptr.add(dest + i);
+        dest_ptr.write_volatile(src_ptr.read_volatile());
+    });
+}
+
+/// Copies `LEN` elements from index `SRC` to index `DEST` within an array, with
+/// the bounds checked at compile time.
+///
+/// This is like [`copy_in_place`] with constant offsets. If either range
+/// exceeds the end of the array, the call fails to compile instead of
+/// panicking. It's also a `const fn`, so it can be used to build arrays in
+/// constant contexts.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_in_place_const;
+/// const GREETING: [u8; 13] = {
+///     let mut bytes = *b"Hello, World!";
+///     copy_in_place_const::<1, 4, 8, _, _>(&mut bytes);
+///     bytes
+/// };
+///
+/// assert_eq!(&GREETING, b"Hello, Wello!");
+/// ```
+///
+/// An out-of-bounds copy doesn't compile:
+///
+/// ```compile_fail
+/// # use copy_in_place::copy_in_place_const;
+/// let mut bytes = *b"Hello, World!";
+/// copy_in_place_const::<1, 4, 10, _, _>(&mut bytes);
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub const fn copy_in_place_const<
+    const SRC: usize,
+    const LEN: usize,
+    const DEST: usize,
+    T: Copy,
+    const N: usize,
+>(
+    array: &mut [T; N],
+) {
+    const {
+        assert!(SRC <= N && LEN <= N - SRC, "src is out of bounds");
+        assert!(DEST <= N - LEN, "dest is out of bounds");
+    }
+    copy_in_place_const_range(array, SRC, SRC + LEN, DEST);
+}
+
+/// Copies elements from `src_start..src_end` to `dest` within a slice, as a
+/// `const fn`.
+///
+/// This is like [`copy_in_place`], but it takes the source range as two
+/// indices rather than a `RangeBounds`, so that it can be called during
+/// constant evaluation.
+///
+/// # Panics
+///
+/// This function will panic if either range exceeds the end of the slice, or if
+/// `src_end` is before `src_start`. In a constant context, that panic is a
+/// compile error.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_in_place_const_range;
+/// const GREETING: [u8; 13] = {
+///     let mut bytes = *b"Hello, World!";
+///     copy_in_place_const_range(&mut bytes, 1, 5, 8);
+///     bytes
+/// };
+///
+/// assert_eq!(&GREETING, b"Hello, Wello!");
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub const fn copy_in_place_const_range<T: Copy>(
+    slice: &mut [T],
+    src_start: usize,
+    src_end: usize,
+    dest: usize,
+) {
+    assert!(src_start <= src_end, "src end is before src start");
+    assert!(src_end <= slice.len(), "src is out of bounds");
+    let count = src_end - src_start;
+    assert!(dest <= slice.len() - count, "dest is out of bounds");
+    // Derive both `src_ptr` and `dest_ptr` from the same loan
+    unsafe { memmove(slice.as_mut_ptr(), src_start, count, dest) };
+}
+
+/// Copies bytes from one part of a string to another part of the same string,
+/// like [`copy_in_place`], keeping the string valid UTF-8.
+///
+/// `src` is a byte range, and `dest` is the byte index to copy to. Both ends of
+/// `src` and both ends of the destination range must be on char boundaries.
+/// Then the copied bytes are whole chars, and the bytes around the destination
+/// are untouched whole chars, so both ends of the destination are still char
+/// boundaries after the write.
+///
+/// # Panics
+///
+/// This function will panic if either range exceeds the end of the string, if
+/// the end of `src` is before the start, or if any end of either range is not
+/// on a char boundary.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_in_place_str;
+/// let mut s = String::from("Grüße, Jürgen ❤");
+///
+/// copy_in_place_str(&mut s, 0..4, 9);
+///
+/// assert_eq!(s, "Grüße, Grügen ❤");
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn copy_in_place_str<R: RangeBounds<usize>>(s: &mut str, src: R, dest: usize) {
+    let (src_start, count) = match resolve_copy(&src, dest, s.len()) {
+        Ok(resolved) => resolved,
+        Err(e) => panic!("{}", e),
+    };
+    assert!(
+        s.is_char_boundary(src_start),
+        "src start is not on a char boundary"
+    );
+    assert!(
+        s.is_char_boundary(src_start + count),
+        "src end is not on a char boundary"
+    );
+    assert!(s.is_char_boundary(dest), "dest is not on a char boundary");
+    assert!(
+        s.is_char_boundary(dest + count),
+        "dest end is not on a char boundary"
+    );
+    // The checks above guarantee that the string is still valid UTF-8 after the
+    // copy.
+    copy_in_place(
+        unsafe { s.as_bytes_mut() },
+        src_start..src_start + count,
+        dest,
+    );
+}
+
+/// Removes a range from the live prefix of a slice by shifting the elements
+/// after it to the left, and returns the new length of the prefix.
+///
+/// This is for fixed-capacity storage, where the first `len` elements of
+/// `slice` are in use. Elements from the end of `range` up to `len` are moved
+/// down to the start of `range` with [`copy_in_place`]. The elements between
+/// the new length and `len` keep whatever values they had; use
+/// [`remove_range_in_place_fill`] to overwrite them.
+///
+/// # Panics
+///
+/// This function will panic if `len` exceeds `slice.len()`, if `range` exceeds
+/// `len`, or if the end of `range` is before the start.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::remove_range_in_place;
+/// let mut buf = *b"Hello, World!...";
+///
+/// let len = remove_range_in_place(&mut buf, 13, 5..12);
+///
+/// assert_eq!(len, 6);
+/// assert_eq!(&buf[..len], b"Hello!");
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+/// [`remove_range_in_place_fill`]: fn.remove_range_in_place_fill.html
+pub fn remove_range_in_place<T: Copy, R: RangeBounds<usize>>(
+    slice: &mut [T],
+    len: usize,
+    range: R,
+) -> usize {
+    assert!(len <= slice.len(), "len is out of bounds");
+    let (start, end) = resolve_range_or_panic(&range, len);
+    copy_in_place(slice, end..len, start);
+    len - (end - start)
+}
+
+/// Like [`remove_range_in_place`], but also overwrites the elements freed at
+/// the end of the live prefix with `fill`.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::remove_range_in_place_fill;
+/// let mut buf = *b"Hello, World!...";
+///
+/// let len = remove_range_in_place_fill(&mut buf, 13, 5..12, b'.');
+///
+/// assert_eq!(len, 6);
+/// assert_eq!(&buf, b"Hello!..........");
+/// ```
+///
+/// [`remove_range_in_place`]: fn.remove_range_in_place.html
+pub fn remove_range_in_place_fill<T: Copy, R: RangeBounds<usize>>(
+    slice: &mut [T],
+    len: usize,
+    range: R,
+    fill: T,
+) -> usize {
+    let new_len = remove_range_in_place(slice, len, range);
+    for element in &mut slice[new_len..len] {
+        *element = fill;
+    }
+    new_len
+}
+
+/// Opens a gap of `n` elements at `pos` in the live prefix of a slice by
+/// shifting the elements after it to the right, and returns the gap.
+///
+/// This is for fixed-capacity storage, where the first `len` elements of
+/// `slice` are in use. Elements from `pos` up to `len` are moved up by `n` with
+/// [`copy_in_place`], so the live prefix grows to `len + n` elements. The
+/// returned gap `pos..pos + n` still holds its old values, for the caller to
+/// overwrite.
+///
+/// # Panics
+///
+/// This function will panic if `len` exceeds `slice.len()`, if `pos` exceeds
+/// `len`, or if `len + n` exceeds `slice.len()`. See
+/// [`try_insert_gap_in_place`] for a non-panicking version.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::insert_gap_in_place;
+/// let mut buf = *b"Hello!.......";
+///
+/// insert_gap_in_place(&mut buf, 6, 5, 7).copy_from_slice(b", World");
+///
+/// assert_eq!(&buf, b"Hello, World!");
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+/// [`try_insert_gap_in_place`]: fn.try_insert_gap_in_place.html
+pub fn insert_gap_in_place<T: Copy>(slice: &mut [T], len: usize, pos: usize, n: usize) -> &mut [T] {
+    match try_insert_gap_in_place(slice, len, pos, n) {
+        Ok(gap) => gap,
+        Err(e) => panic!("{}", e),
+    }
+}
 
-use core::ops::Bound;
-use core::ops::RangeBounds;
+/// Like [`insert_gap_in_place`], but returns a [`CopyError`] instead of
+/// panicking. The slice is not modified on error.
+///
+/// The errors are [`CopyError::LenOutOfBounds`],
+/// [`CopyError::PosOutOfBounds`] and [`CopyError::GapOutOfBounds`], in the
+/// order of the panics documented on [`insert_gap_in_place`].
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::{try_insert_gap_in_place, CopyError};
+/// let mut buf = *b"Hello!...";
+///
+/// assert_eq!(
+///     try_insert_gap_in_place(&mut buf, 6, 5, 7),
+///     Err(CopyError::GapOutOfBounds { len: 6, n: 7, capacity: 9 }),
+/// );
+/// ```
+///
+/// [`insert_gap_in_place`]: fn.insert_gap_in_place.html
+/// [`CopyError`]: enum.CopyError.html
+/// [`CopyError::LenOutOfBounds`]: enum.CopyError.html#variant.LenOutOfBounds
+/// [`CopyError::PosOutOfBounds`]: enum.CopyError.html#variant.PosOutOfBounds
+/// [`CopyError::GapOutOfBounds`]: enum.CopyError.html#variant.GapOutOfBounds
+pub fn try_insert_gap_in_place<T: Copy>(
+    slice: &mut [T],
+    len: usize,
+    pos: usize,
+    n: usize,
+) -> Result<&mut [T], CopyError> {
+    let capacity = slice.len();
+    if len > capacity {
+        return Err(CopyError::LenOutOfBounds { len, capacity });
+    }
+    if pos > len {
+        return Err(CopyError::PosOutOfBounds { pos, len });
+    }
+    if n > capacity - len {
+        return Err(CopyError::GapOutOfBounds { len, n, capacity });
+    }
+    copy_in_place(slice, pos..len, pos + n);
+    Ok(&mut slice[pos..pos + n])
+}
 
-/// Copies elements from one part of a slice to another part of the same
-/// slice, using a memmove.
+/// One copy for [`copy_many`], from `src_start..src_end` to `dest`.
 ///
-/// `src` is the range within the slice to copy from. `dest` is the starting
-/// index of the range within the slice to copy to, which will have the same
-/// length as `src`. The two ranges may overlap. The ends of the two ranges must
-/// be less than or equal to `slice.len()`.
+/// [`copy_many`]: fn.copy_many.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CopyOp {
+    pub src_start: usize,
+    pub src_end: usize,
+    pub dest: usize,
+}
+
+/// Applies a batch of copies within a slice, checking all of them before
+/// modifying anything.
+///
+/// Each [`CopyOp`] has the same meaning as a call to [`copy_in_place`], and the
+/// ops are applied in order, so later ops see the results of earlier ones.
+/// Because copies never change the length of the slice, validating every op up
+/// front means the batch can't fail halfway through.
 ///
 /// # Panics
 ///
-/// This function will panic if either range exceeds the end of the slice, or if
-/// the end of `src` is before the start.
+/// This function will panic, without modifying the slice, if any op has a range
+/// that exceeds the end of the slice or a `src_end` before its `src_start`. See
+/// [`try_copy_many`] for a non-panicking version.
 ///
 /// # Examples
 ///
-/// Copying four bytes within a slice:
+/// ```
+/// # use copy_in_place::{copy_many, CopyOp};
+/// let mut bytes = *b"Hello, World!";
 ///
+/// copy_many(&mut bytes, &[
+///     CopyOp { src_start: 7, src_end: 12, dest: 0 },
+///     CopyOp { src_start: 0, src_end: 1, dest: 7 },
+/// ]);
+///
+/// assert_eq!(&bytes, b"World, World!");
 /// ```
-/// # use copy_in_place::copy_in_place;
+///
+/// [`CopyOp`]: struct.CopyOp.html
+/// [`copy_in_place`]: fn.copy_in_place.html
+/// [`try_copy_many`]: fn.try_copy_many.html
+pub fn copy_many<T: Copy>(slice: &mut [T], ops: &[CopyOp]) {
+    if let Err(e) = try_copy_many(slice, ops) {
+        panic!("{}", e);
+    }
+}
+
+/// Like [`copy_many`], but returns a [`CopyManyError`] for the first invalid op
+/// instead of panicking. The slice is not modified on error.
+///
+/// [`copy_many`]: fn.copy_many.html
+/// [`CopyManyError`]: struct.CopyManyError.html
+pub fn try_copy_many<T: Copy>(slice: &mut [T], ops: &[CopyOp]) -> Result<(), CopyManyError> {
+    for (index, op) in ops.iter().enumerate() {
+        resolve_copy(&(op.src_start..op.src_end), op.dest, slice.len())
+            .map_err(|error| CopyManyError { index, error })?;
+    }
+    // Derive both `src_ptr` and `dest_ptr` from the same loan
+    let ptr = slice.as_mut_ptr();
+    for op in ops {
+        unsafe { memmove(ptr, op.src_start, op.src_end - op.src_start, op.dest) };
+    }
+    Ok(())
+}
+
+/// Copies elements within a slice like [`copy_in_place`], but clips the copy
+/// to the slice instead of panicking, and returns the range that was written.
+///
+/// The parts of `src` past the end of the slice are ignored. `dest` may be
+/// negative, in which case the elements that would land before the start of
+/// the slice are skipped, and so are the elements that would land past the
+/// end. If `src` is empty after clipping, including when its end is before
+/// its start, nothing is copied.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_in_place_clipped;
 /// let mut bytes = *b"Hello, World!";
 ///
-/// copy_in_place(&mut bytes, 1..5, 8);
+/// assert_eq!(copy_in_place_clipped(&mut bytes, 7.., 10), 10..13);
+/// assert_eq!(&bytes, b"Hello, WorWor");
 ///
-/// assert_eq!(&bytes, b"Hello, Wello!");
+/// assert_eq!(copy_in_place_clipped(&mut bytes, 0..5, -2), 0..3);
+/// assert_eq!(&bytes, b"llolo, WorWor");
 /// ```
-pub fn copy_in_place<T: Copy, R: RangeBounds<usize>>(slice: &mut [T], src: R, dest: usize) {
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn copy_in_place_clipped<T: Copy, R: RangeBounds<usize>>(
+    slice: &mut [T],
+    src: R,
+    dest: isize,
+) -> Range<usize> {
     let src_start = match src.start_bound() {
         Bound::Included(&n) => n,
-        Bound::Excluded(&n) => n.checked_add(1).expect("range bound overflows usize"),
+        Bound::Excluded(&n) => n.saturating_add(1),
         Bound::Unbounded => 0,
     };
     let src_end = match src.end_bound() {
-        Bound::Included(&n) => n.checked_add(1).expect("range bound overflows usize"),
+        Bound::Included(&n) => n.saturating_add(1),
         Bound::Excluded(&n) => n,
         Bound::Unbounded => slice.len(),
     };
-    assert!(src_start <= src_end, "src end is before src start");
-    assert!(src_end <= slice.len(), "src is out of bounds");
-    let count = src_end - src_start;
-    assert!(dest <= slice.len() - count, "dest is out of bounds");
-    unsafe {
-        // Derive both `src_ptr` and `dest_ptr` from the same loan
-        let ptr = slice.as_mut_ptr();
-        let src_ptr = ptr.add(src_start);
-        let dest_ptr = ptr.add(dest);
-        core::ptr::copy(src_ptr, dest_ptr, count);
-    }
+    let (src_start, dest, count) = clip_span(src_start, src_end, dest, slice.len());
+    copy_in_place(slice, src_start..src_start + count, dest);
+    dest..dest + count
+}
+
+/// Clips a copy of `src_start..src_end` to a signed `dest`, within a dimension
+/// of length `len`. Returns the clipped `(src_start, dest, count)`.
+fn clip_span(src_start: usize, src_end: usize, dest: isize, len: usize) -> (usize, usize, usize) {
+    let src_end = core::cmp::min(src_end, len);
+    let mut src_start = core::cmp::min(src_start, src_end);
+    let dest = if dest < 0 {
+        // Skip the elements that would land before the start.
+        src_start = core::cmp::min(src_start.saturating_add(dest.unsigned_abs()), src_end);
+        0
+    } else {
+        core::cmp::min(dest as usize, len)
+    };
+    let count = core::cmp::min(src_end - src_start, len - dest);
+    (src_start, dest, count)
 }
 
 #[test]
@@ -113,3 +1247,538 @@ fn test_empty_slice() {
     copy_in_place(&mut array, 0..0, 0);
     assert_eq!(array, []);
 }
+
+#[test]
+fn test_try_errors() {
+    let mut array = *b"Hello, World!";
+    assert_eq!(
+        try_copy_in_place(&mut array, (Bound::Included(5), Bound::Excluded(1)), 0),
+        Err(CopyError::SrcEndBeforeStart { start: 5, end: 1 })
+    );
+    assert_eq!(
+        try_copy_in_place(&mut array, 1..14, 0),
+        Err(CopyError::SrcOutOfBounds { end: 14, len: 13 })
+    );
+    assert_eq!(
+        try_copy_in_place(&mut array, 1..5, 10),
+        Err(CopyError::DestOutOfBounds {
+            dest: 10,
+            count: 4,
+            len: 13
+        })
+    );
+    assert_eq!(
+        try_copy_in_place(&mut array, ..=usize::MAX, 0),
+        Err(CopyError::RangeOverflow)
+    );
+    assert_eq!(&array, b"Hello, World!");
+}
+
+#[cfg(test)]
+fn naive_backref<T: Copy>(slice: &mut [T], pos: usize, distance: usize, length: usize) {
+    for i in pos..pos + length {
+        slice[i] = slice[i - distance];
+    }
+}
+
+#[test]
+fn test_backref_run() {
+    let mut array = *b"Hello, World!";
+    copy_backref(&mut array, 1, 1, 12);
+    assert_eq!(&array, b"HHHHHHHHHHHHH");
+}
+
+#[test]
+fn test_backref_against_naive() {
+    let original: [u8; 40] = core::array::from_fn(|i| i as u8);
+    for pos in 1..=original.len() {
+        for distance in 1..=pos {
+            for length in 0..=original.len() - pos {
+                let mut expected = original;
+                naive_backref(&mut expected, pos, distance, length);
+                let mut array = original;
+                copy_backref(&mut array, pos, distance, length);
+                assert_eq!(array, expected, "{} {} {}", pos, distance, length);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_backref_distance_out_of_bounds() {
+    let mut array = *b"Hello, World!";
+    copy_backref(&mut array, 2, 3, 1);
+}
+
+#[test]
+#[should_panic]
+fn test_backref_length_out_of_bounds() {
+    let mut array = *b"Hello, World!";
+    copy_backref(&mut array, 10, 3, 4);
+}
+
+#[test]
+fn test_swap_ranges() {
+    let mut array = *b"Hello, World!";
+    swap_ranges_in_place(&mut array, 7..12, 0);
+    assert_eq!(&array, b"World, Hello!");
+}
+
+#[test]
+fn test_swap_ranges_adjacent() {
+    let mut array = *b"abcdef";
+    swap_ranges_in_place(&mut array, ..3, 3);
+    assert_eq!(&array, b"defabc");
+}
+
+#[test]
+fn test_swap_ranges_overlap() {
+    let mut array = *b"Hello, World!";
+    assert_eq!(
+        try_swap_ranges_in_place(&mut array, 1..5, 4),
+        Err(CopyError::Overlap {
+            src_start: 1,
+            dest: 4,
+            count: 4
+        })
+    );
+    assert_eq!(try_swap_ranges_in_place(&mut array, 1..1, 1), Ok(()));
+    assert_eq!(&array, b"Hello, World!");
+}
+
+#[test]
+#[should_panic]
+fn test_swap_ranges_out_of_bounds() {
+    let mut array = *b"Hello, World!";
+    swap_ranges_in_place(&mut array, 1..5, 10);
+}
+
+#[test]
+fn test_move_range_forward() {
+    let mut array = *b"Hello, World!";
+    move_range_to(&mut array, ..5, 6);
+    assert_eq!(&array, b", WorlHellod!");
+}
+
+#[test]
+fn test_move_range_backward() {
+    let mut array = *b"Hello, World!";
+    move_range_to(&mut array, 7..=11, 0);
+    assert_eq!(&array, b"WorldHello, !");
+}
+
+#[test]
+fn test_move_range_noop() {
+    let mut array = *b"Hello, World!";
+    move_range_to(&mut array, 3..7, 3);
+    move_range_to(&mut array, 5..5, 0);
+    move_range_to(&mut array, .., 0);
+    assert_eq!(&array, b"Hello, World!");
+}
+
+#[test]
+fn test_move_range_not_copy() {
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+    let mut items: Vec<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+    move_range_to(&mut items, 2.., 1);
+    assert_eq!(items, ["a", "c", "d", "b"]);
+}
+
+#[test]
+#[should_panic]
+fn test_move_range_out_of_bounds() {
+    let mut array = *b"Hello, World!";
+    move_range_to(&mut array, 1..5, 10);
+}
+
+#[test]
+fn test_clone_overlapping() {
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+    let original: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+    for src_start in 0..=original.len() {
+        for src_end in src_start..=original.len() {
+            for dest in 0..=original.len() - (src_end - src_start) {
+                let copies = original[src_start..src_end].to_vec();
+                let mut expected = original.clone();
+                expected[dest..dest + copies.len()].clone_from_slice(&copies);
+                let mut items = original.clone();
+                clone_in_place(&mut items, src_start..src_end, dest);
+                assert_eq!(items, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_clone_panic_safety() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    struct Tracked {
+        id: usize,
+        live: Rc<Cell<isize>>,
+        clones_left: Rc<Cell<usize>>,
+    }
+
+    impl Clone for Tracked {
+        fn clone(&self) -> Self {
+            if self.clones_left.get() == 0 {
+                panic!("clone failed");
+            }
+            self.clones_left.set(self.clones_left.get() - 1);
+            self.live.set(self.live.get() + 1);
+            Tracked {
+                id: self.id,
+                live: self.live.clone(),
+                clones_left: self.clones_left.clone(),
+            }
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.live.set(self.live.get() - 1);
+        }
+    }
+
+    let live = Rc::new(Cell::new(0));
+    let clones_left = Rc::new(Cell::new(2));
+    let mut items: Vec<Tracked> = (0..6)
+        .map(|id| {
+            live.set(live.get() + 1);
+            Tracked {
+                id,
+                live: live.clone(),
+                clones_left: clones_left.clone(),
+            }
+        })
+        .collect();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        clone_in_place(&mut items, 0..4, 2);
+    }));
+    assert!(result.is_err());
+    // Copying backwards, elements 3 and 2 were cloned into 5 and 4, and then
+    // the third clone panicked.
+    let ids: Vec<usize> = items.iter().map(|item| item.id).collect();
+    assert_eq!(ids, [0, 1, 2, 3, 2, 3]);
+    assert_eq!(live.get(), 6);
+    drop(items);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_trait_method() {
+    let mut array = *b"Hello, World!";
+    array.copy_in_place(1..5, 8);
+    assert_eq!(&array, b"Hello, Wello!");
+    array[..].copy_in_place(1..5, 2);
+    assert_eq!(&array, b"Heello Wello!");
+    assert_eq!(
+        array.try_copy_in_place(1..5, 10),
+        Err(CopyError::DestOutOfBounds {
+            dest: 10,
+            count: 4,
+            len: 13
+        })
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_trait_alloc() {
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+
+    fn generic<C: CopyInPlace + ?Sized>(buf: &mut C) {
+        buf.copy_in_place(1..5, 8);
+    }
+
+    let mut vec: Vec<u8> = b"Hello, World!".to_vec();
+    generic(&mut vec);
+    assert_eq!(vec, b"Hello, Wello!");
+    let mut boxed: Box<[u8]> = b"Hello, World!".to_vec().into_boxed_slice();
+    generic(&mut boxed);
+    assert_eq!(&*boxed, b"Hello, Wello!");
+}
+
+#[test]
+fn test_cells_aliasing_views() {
+    let mut array = *b"Hello, World!";
+    {
+        let cells = Cell::from_mut(&mut array[..]).as_slice_of_cells();
+        let hello = &cells[..5];
+        let world = &cells[7..12];
+        copy_in_place_cells(cells, 1..5, 2);
+        assert_eq!(hello[4].get(), b'l');
+        copy_in_place_cells(hello, 0..2, 3);
+        assert_eq!(cells[4].get(), b'e');
+        copy_in_place_cells(cells, .., 0);
+        copy_in_place_cells(world, 3.., 0);
+        assert_eq!(cells[7].get(), b'l');
+    }
+    assert_eq!(&array, b"HeeHeo ldrld!");
+}
+
+#[test]
+#[should_panic]
+fn test_cells_out_of_bounds() {
+    let mut array = *b"Hello, World!";
+    let cells = Cell::from_mut(&mut array[..]).as_slice_of_cells();
+    copy_in_place_cells(cells, 1..5, 10);
+}
+
+#[test]
+fn test_volatile_overlap() {
+    let original = [0u16, 1, 2, 3, 4, 5, 6];
+    for src_start in 0..=original.len() {
+        for src_end in src_start..=original.len() {
+            for dest in 0..=original.len() - (src_end - src_start) {
+                let mut expected = original;
+                copy_in_place(&mut expected, src_start..src_end, dest);
+                let mut array = original;
+                copy_in_place_volatile(&mut array, src_start..src_end, dest);
+                assert_eq!(array, expected);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_volatile_out_of_bounds() {
+    let mut array = *b"Hello, World!";
+    copy_in_place_volatile(&mut array, 1..5, 10);
+}
+
+#[test]
+fn test_const_generic() {
+    const ARRAY: [u8; 13] = {
+        let mut array = *b"Hello, World!";
+        copy_in_place_const::<1, 4, 2, _, _>(&mut array);
+        array
+    };
+    assert_eq!(&ARRAY, b"Heello World!");
+    let mut array = *b"Hello, World!";
+    copy_in_place_const::<0, 13, 0, _, _>(&mut array);
+    copy_in_place_const::<13, 0, 13, _, _>(&mut array);
+    assert_eq!(&array, b"Hello, World!");
+}
+
+#[test]
+fn test_const_range() {
+    const ARRAY: [u8; 13] = {
+        let mut array = *b"Hello, World!";
+        copy_in_place_const_range(&mut array, 1, 5, 2);
+        copy_in_place_const_range(&mut array, 1, 1, 13);
+        array
+    };
+    assert_eq!(&ARRAY, b"Heello World!");
+    let mut array = *b"Hello, World!";
+    copy_in_place_const_range(&mut array[1..], 0, 4, 7);
+    assert_eq!(&array, b"Hello, Wello!");
+}
+
+#[test]
+#[should_panic(expected = "dest is out of bounds")]
+fn test_const_range_out_of_bounds() {
+    let mut array = *b"Hello, World!";
+    copy_in_place_const_range(&mut array, 1, 5, 10);
+}
+
+#[test]
+#[should_panic(expected = "src end is before src start")]
+fn test_const_range_backwards() {
+    let mut array = *b"Hello, World!";
+    copy_in_place_const_range(&mut array, 5, 1, 0);
+}
+
+#[test]
+fn test_str() {
+    let mut s = std::string::String::from("Hello, World!");
+    copy_in_place_str(&mut s, 1..5, 8);
+    assert_eq!(s, "Hello, Wello!");
+    let mut s = std::string::String::from("αβγδ");
+    copy_in_place_str(&mut s, 2.., 0);
+    assert_eq!(s, "βγδδ");
+}
+
+#[test]
+#[should_panic(expected = "src start is not on a char boundary")]
+fn test_str_src_start_boundary() {
+    let mut s = std::string::String::from("αβγδ");
+    copy_in_place_str(&mut s, 1..3, 4);
+}
+
+#[test]
+#[should_panic(expected = "src end is not on a char boundary")]
+fn test_str_src_end_boundary() {
+    let mut s = std::string::String::from("αβγδ");
+    copy_in_place_str(&mut s, 0..3, 4);
+}
+
+#[test]
+#[should_panic(expected = "dest is not on a char boundary")]
+fn test_str_dest_boundary() {
+    let mut s = std::string::String::from("aβγδ");
+    copy_in_place_str(&mut s, 0..1, 2);
+}
+
+#[test]
+#[should_panic(expected = "dest end is not on a char boundary")]
+fn test_str_dest_end_boundary() {
+    let mut s = std::string::String::from("aβγδ");
+    copy_in_place_str(&mut s, 0..1, 1);
+}
+
+#[test]
+fn test_remove_range() {
+    let mut array = *b"Hello, World!...";
+    assert_eq!(remove_range_in_place(&mut array, 13, ..7), 6);
+    assert_eq!(&array, b"World! World!...");
+    assert_eq!(remove_range_in_place(&mut array, 6, 5..), 5);
+    assert_eq!(remove_range_in_place(&mut array, 5, 2..2), 5);
+    assert_eq!(&array[..5], b"World");
+    assert_eq!(remove_range_in_place_fill(&mut array, 5, 1..=3, b'_'), 2);
+    assert_eq!(&array, b"Wd___! World!...");
+}
+
+#[test]
+#[should_panic(expected = "range is out of bounds (end: 14, len: 13)")]
+fn test_remove_range_past_len() {
+    let mut array = *b"Hello, World!...";
+    remove_range_in_place(&mut array, 13, 10..14);
+}
+
+#[test]
+#[should_panic(expected = "len is out of bounds")]
+fn test_remove_range_len_out_of_bounds() {
+    let mut array = *b"Hello, World!...";
+    remove_range_in_place(&mut array, 17, 0..1);
+}
+
+#[test]
+fn test_insert_gap() {
+    let mut array = *b"World!.......";
+    let gap = insert_gap_in_place(&mut array, 6, 0, 7);
+    assert_eq!(gap, b"World!.");
+    gap.copy_from_slice(b"Hello, ");
+    assert_eq!(&array, b"Hello, World!");
+    assert_eq!(insert_gap_in_place(&mut array, 13, 13, 0), b"");
+}
+
+#[test]
+fn test_insert_gap_errors() {
+    let mut array = *b"Hello!...";
+    assert_eq!(
+        try_insert_gap_in_place(&mut array, 10, 0, 0),
+        Err(CopyError::LenOutOfBounds {
+            len: 10,
+            capacity: 9
+        })
+    );
+    assert_eq!(
+        try_insert_gap_in_place(&mut array, 6, 7, 1),
+        Err(CopyError::PosOutOfBounds { pos: 7, len: 6 })
+    );
+    assert_eq!(
+        try_insert_gap_in_place(&mut array, 6, 1, usize::MAX),
+        Err(CopyError::GapOutOfBounds {
+            len: 6,
+            n: usize::MAX,
+            capacity: 9
+        })
+    );
+    assert_eq!(
+        try_insert_gap_in_place(&mut array, 6, 5, 4),
+        Err(CopyError::GapOutOfBounds {
+            len: 6,
+            n: 4,
+            capacity: 9
+        })
+    );
+    assert_eq!(&array, b"Hello!...");
+}
+
+#[test]
+#[should_panic(expected = "len is out of bounds (len: 12, capacity: 9)")]
+fn test_insert_gap_len_out_of_bounds() {
+    let mut array = *b"Hello!...";
+    insert_gap_in_place(&mut array, 12, 0, 0);
+}
+
+#[test]
+fn test_copy_many() {
+    let mut array = *b"Hello, World!";
+    let ops = [
+        CopyOp {
+            src_start: 1,
+            src_end: 5,
+            dest: 8,
+        },
+        CopyOp {
+            src_start: 1,
+            src_end: 5,
+            dest: 2,
+        },
+    ];
+    copy_many(&mut array, &ops);
+    assert_eq!(&array, b"Heello Wello!");
+    copy_many(&mut array, &[]);
+    assert_eq!(&array, b"Heello Wello!");
+}
+
+#[test]
+fn test_copy_many_validates_first() {
+    let mut array = *b"Hello, World!";
+    let ops = [
+        CopyOp {
+            src_start: 1,
+            src_end: 5,
+            dest: 8,
+        },
+        CopyOp {
+            src_start: 5,
+            src_end: 1,
+            dest: 0,
+        },
+        CopyOp {
+            src_start: 1,
+            src_end: 5,
+            dest: 10,
+        },
+    ];
+    assert_eq!(
+        try_copy_many(&mut array, &ops),
+        Err(CopyManyError {
+            index: 1,
+            error: CopyError::SrcEndBeforeStart { start: 5, end: 1 }
+        })
+    );
+    assert_eq!(&array, b"Hello, World!");
+}
+
+#[test]
+fn test_clipped() {
+    let mut array = *b"Hello, World!";
+    assert_eq!(copy_in_place_clipped(&mut array, 1..5, 8), 8..12);
+    assert_eq!(&array, b"Hello, Wello!");
+    assert_eq!(copy_in_place_clipped(&mut array, 0..100, 10), 10..13);
+    assert_eq!(&array, b"Hello, WelHel");
+    assert_eq!(copy_in_place_clipped(&mut array, ..=usize::MAX, -10), 0..3);
+    assert_eq!(&array, b"Hello, WelHel");
+    assert_eq!(copy_in_place_clipped(&mut array, 7.., -1), 0..5);
+    assert_eq!(&array, b"elHel, WelHel");
+    assert_eq!(copy_in_place_clipped(&mut array, 1..5, isize::MIN), 0..0);
+    assert_eq!(copy_in_place_clipped(&mut array, 1..5, 13), 13..13);
+    assert_eq!(copy_in_place_clipped(&mut array, 1..5, isize::MAX), 13..13);
+    assert_eq!(copy_in_place_clipped(&mut array, 20..30, 0), 0..0);
+    assert_eq!(
+        copy_in_place_clipped(&mut array, (Bound::Excluded(5), Bound::Included(1)), 0),
+        0..0
+    );
+    assert_eq!(&array, b"elHel, WelHel");
+}
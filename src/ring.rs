@@ -0,0 +1,201 @@
+use core::cmp;
+use core::ops::RangeBounds;
+
+use super::{copy_in_place, resolve_copy};
+
+/// Copies elements within a ring buffer, using logical indices that wrap
+/// around the end of the underlying storage.
+///
+/// `buf` is the ring buffer's storage. Its logical contents are the `len`
+/// elements starting at physical index `head` and wrapping around to the front
+/// of `buf`. `src` and `dest` are logical indices with the same meaning as in
+/// [`copy_in_place`], and the two ranges may overlap. The copy is done with at
+/// most three memmoves.
+///
+/// # Panics
+///
+/// This function will panic if `len` exceeds `buf.len()`, if `head` is greater
+/// than `buf.len()`, if either range exceeds `len`, or if the end of `src` is
+/// before the start.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_in_place_ring;
+/// // The logical contents are "abcdef", starting at index 6.
+/// let mut buf = *b"cdef..ab";
+///
+/// copy_in_place_ring(&mut buf, 6, 6, 0..3, 2);
+///
+/// assert_eq!(&buf, b"abcf..ab");
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+pub fn copy_in_place_ring<T: Copy, R: RangeBounds<usize>>(
+    buf: &mut [T],
+    head: usize,
+    len: usize,
+    src: R,
+    dest: usize,
+) {
+    assert!(len <= buf.len(), "len is out of bounds");
+    assert!(head <= buf.len(), "head is out of bounds");
+    if len <= buf.len() - head {
+        copy_in_place(&mut buf[head..head + len], src, dest);
+    } else {
+        let back_len = len - (buf.len() - head);
+        let (back, front) = buf.split_at_mut(head);
+        copy_in_place_split(front, &mut back[..back_len], src, dest);
+    }
+}
+
+/// Copies elements within a `VecDeque`, like [`copy_in_place`].
+///
+/// This is a convenience wrapper around the same logic as
+/// [`copy_in_place_ring`], operating on the slices returned by
+/// `VecDeque::as_mut_slices`.
+///
+/// # Panics
+///
+/// This function will panic if either range exceeds the end of the deque, or
+/// if the end of `src` is before the start.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::copy_in_place_vec_deque;
+/// use std::collections::VecDeque;
+///
+/// let mut deque: VecDeque<u8> = b"Hello, World!".iter().copied().collect();
+///
+/// copy_in_place_vec_deque(&mut deque, 1..5, 8);
+///
+/// assert!(deque.iter().eq(b"Hello, Wello!"));
+/// ```
+///
+/// [`copy_in_place`]: fn.copy_in_place.html
+/// [`copy_in_place_ring`]: fn.copy_in_place_ring.html
+#[cfg(feature = "alloc")]
+pub fn copy_in_place_vec_deque<T: Copy, R: RangeBounds<usize>>(
+    deque: &mut alloc::collections::VecDeque<T>,
+    src: R,
+    dest: usize,
+) {
+    let (front, back) = deque.as_mut_slices();
+    copy_in_place_split(front, back, src, dest);
+}
+
+/// Copies within the logical sequence formed by `front` followed by `back`.
+fn copy_in_place_split<T: Copy, R: RangeBounds<usize>>(
+    front: &mut [T],
+    back: &mut [T],
+    src: R,
+    dest: usize,
+) {
+    let len = front.len() + back.len();
+    let (src_start, count) = match resolve_copy(&src, dest, len) {
+        Ok(resolved) => resolved,
+        Err(e) => panic!("{}", e),
+    };
+    let split = front.len();
+    // The number of elements from `i` to the end of the slice that contains it,
+    // and from the start of the slice that contains `i - 1` up to `i`.
+    let contiguous_after = |i: usize| if i < split { split - i } else { len - i };
+    let contiguous_before = |i: usize| if i <= split { i } else { i - split };
+    // Go in the same order as `for_each_memmove_order`, a chunk at a time. Each
+    // chunk is contiguous in both the source and the destination, so it reads
+    // all of its source before writing.
+    if dest <= src_start {
+        let mut done = 0;
+        while done < count {
+            let n = cmp::min(
+                count - done,
+                cmp::min(
+                    contiguous_after(src_start + done),
+                    contiguous_after(dest + done),
+                ),
+            );
+            copy_chunk(front, back, src_start + done, dest + done, n);
+            done += n;
+        }
+    } else {
+        let mut remaining = count;
+        while remaining > 0 {
+            let n = cmp::min(
+                remaining,
+                cmp::min(
+                    contiguous_before(src_start + remaining),
+                    contiguous_before(dest + remaining),
+                ),
+            );
+            remaining -= n;
+            copy_chunk(front, back, src_start + remaining, dest + remaining, n);
+        }
+    }
+}
+
+/// Copies `n` elements, where the source and the destination each lie entirely
+/// within `front` or entirely within `back`.
+fn copy_chunk<T: Copy>(front: &mut [T], back: &mut [T], src: usize, dest: usize, n: usize) {
+    let split = front.len();
+    match (src < split, dest < split) {
+        (true, true) => copy_in_place(front, src..src + n, dest),
+        (false, false) => copy_in_place(back, src - split..src - split + n, dest - split),
+        (true, false) => back[dest - split..dest - split + n].copy_from_slice(&front[src..src + n]),
+        (false, true) => front[dest..dest + n].copy_from_slice(&back[src - split..src - split + n]),
+    }
+}
+
+#[cfg(test)]
+fn logical<T: Copy>(buf: &[T], head: usize, len: usize) -> std::vec::Vec<T> {
+    (0..len).map(|i| buf[(head + i) % buf.len()]).collect()
+}
+
+#[test]
+fn test_ring_against_contiguous() {
+    let cap = 7;
+    let original: std::vec::Vec<u8> = (0..cap as u8).collect();
+    for head in 0..=cap {
+        for len in 0..=cap {
+            for src_start in 0..=len {
+                for src_end in src_start..=len {
+                    for dest in 0..=len - (src_end - src_start) {
+                        let mut expected = logical(&original, head % cap, len);
+                        copy_in_place(&mut expected, src_start..src_end, dest);
+                        let mut buf = original.clone();
+                        copy_in_place_ring(&mut buf, head, len, src_start..src_end, dest);
+                        assert_eq!(logical(&buf, head % cap, len), expected);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ring_empty() {
+    let mut buf: [u8; 0] = [];
+    copy_in_place_ring(&mut buf, 0, 0, .., 0);
+}
+
+#[test]
+#[should_panic]
+fn test_ring_out_of_bounds() {
+    let mut buf = *b"cdef..ab";
+    copy_in_place_ring(&mut buf, 6, 6, 0..3, 4);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_vec_deque() {
+    use alloc::collections::VecDeque;
+    let mut deque = VecDeque::with_capacity(8);
+    for _ in 0..5 {
+        deque.push_back(0u8);
+        deque.pop_front();
+    }
+    deque.extend(0..8u8);
+    assert!(!deque.as_slices().1.is_empty());
+    copy_in_place_vec_deque(&mut deque, 1..6, 3);
+    assert!(deque.iter().eq([0, 1, 2, 1, 2, 3, 4, 5].iter()));
+}
@@ -0,0 +1,279 @@
+use super::CopyError;
+
+/// Copies `count` elements with a stride from one part of a slice to another
+/// part of the same slice.
+///
+/// Element `i` is copied from index `src_start + i * src_stride` to index
+/// `dest_start + i * dest_stride`. The two sets of elements may overlap. The
+/// result is the same as reading every source element before writing any, as
+/// long as the copy can be done in a single pass, either front to back or back
+/// to front, without overwriting a source element before it's read. If neither
+/// order works, this panics instead. Strides may be zero.
+///
+/// # Panics
+///
+/// This function will panic, without modifying the slice, if either set of
+/// elements extends past the end of the slice, or if no single-pass order
+/// exists. See [`try_copy_strided_in_place`] for a non-panicking version.
+///
+/// # Examples
+///
+/// Copying the left channel of interleaved stereo samples into the right
+/// channel:
+///
+/// ```
+/// # use copy_in_place::copy_strided_in_place;
+/// let mut samples = [1, 0, 2, 0, 3, 0];
+///
+/// copy_strided_in_place(&mut samples, 0, 2, 1, 2, 3);
+///
+/// assert_eq!(samples, [1, 1, 2, 2, 3, 3]);
+/// ```
+///
+/// [`try_copy_strided_in_place`]: fn.try_copy_strided_in_place.html
+pub fn copy_strided_in_place<T: Copy>(
+    slice: &mut [T],
+    src_start: usize,
+    src_stride: usize,
+    dest_start: usize,
+    dest_stride: usize,
+    count: usize,
+) {
+    if let Err(e) =
+        try_copy_strided_in_place(slice, src_start, src_stride, dest_start, dest_stride, count)
+    {
+        panic!("{}", e);
+    }
+}
+
+/// Like [`copy_strided_in_place`], but returns a [`CopyError`] instead of
+/// panicking. The slice is not modified on error.
+///
+/// An element set that extends past the end of the slice is reported as
+/// [`CopyError::StridedSrcOutOfBounds`] or
+/// [`CopyError::StridedDestOutOfBounds`], with the index of its last element,
+/// and a copy with no single-pass order as [`CopyError::NoSinglePassOrder`]. If
+/// the index of either last element overflows `usize`, the error is
+/// [`CopyError::StridedOverflow`].
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::{try_copy_strided_in_place, CopyError};
+/// let mut values = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+///
+/// // Copying front to back, index 2 would be written before it's read as
+/// // source element 1. Copying back to front, index 6 would be written before
+/// // it's read as source element 3.
+/// assert_eq!(
+///     try_copy_strided_in_place(&mut values, 0, 2, 2, 1, 5),
+///     Err(CopyError::NoSinglePassOrder),
+/// );
+/// ```
+///
+/// [`copy_strided_in_place`]: fn.copy_strided_in_place.html
+/// [`CopyError`]: enum.CopyError.html
+/// [`CopyError::StridedSrcOutOfBounds`]: enum.CopyError.html#variant.StridedSrcOutOfBounds
+/// [`CopyError::StridedDestOutOfBounds`]: enum.CopyError.html#variant.StridedDestOutOfBounds
+/// [`CopyError::NoSinglePassOrder`]: enum.CopyError.html#variant.NoSinglePassOrder
+/// [`CopyError::StridedOverflow`]: enum.CopyError.html#variant.StridedOverflow
+pub fn try_copy_strided_in_place<T: Copy>(
+    slice: &mut [T],
+    src_start: usize,
+    src_stride: usize,
+    dest_start: usize,
+    dest_stride: usize,
+    count: usize,
+) -> Result<(), CopyError> {
+    if count == 0 {
+        return Ok(());
+    }
+    let len = slice.len();
+    let src_last = last_index(src_start, src_stride, count)?;
+    if src_last >= len {
+        return Err(CopyError::StridedSrcOutOfBounds {
+            last: src_last,
+            len,
+        });
+    }
+    let dest_last = last_index(dest_start, dest_stride, count)?;
+    if dest_last >= len {
+        return Err(CopyError::StridedDestOutOfBounds {
+            last: dest_last,
+            len,
+        });
+    }
+    let (forward_ok, backward_ok) =
+        single_pass_orders(src_start, src_stride, dest_start, dest_stride, count);
+    let copy = |slice: &mut [T], i: usize| {
+        slice[dest_start + i * dest_stride] = slice[src_start + i * src_stride];
+    };
+    if forward_ok {
+        for i in 0..count {
+            copy(slice, i);
+        }
+    } else if backward_ok {
+        for i in (0..count).rev() {
+            copy(slice, i);
+        }
+    } else {
+        return Err(CopyError::NoSinglePassOrder);
+    }
+    Ok(())
+}
+
+/// Returns `start + (count - 1) * stride`, for `count > 0`.
+fn last_index(start: usize, stride: usize, count: usize) -> Result<usize, CopyError> {
+    (count - 1)
+        .checked_mul(stride)
+        .and_then(|n| n.checked_add(start))
+        .ok_or(CopyError::StridedOverflow {
+            start,
+            stride,
+            count,
+        })
+}
+
+/// Returns whether copying front to back and back to front each give the same
+/// result as reading every source element first. That means no destination
+/// element `i` is also source element `j` with `j` coming after `i` in that
+/// order.
+fn single_pass_orders(
+    src_start: usize,
+    src_stride: usize,
+    dest_start: usize,
+    dest_stride: usize,
+    count: usize,
+) -> (bool, bool) {
+    let mut forward_ok = true;
+    // With a zero destination stride, every element is written to the same
+    // place, and only front to back leaves the last one there.
+    let mut backward_ok = dest_stride != 0 || count == 1;
+    for i in 0..count {
+        let dest = dest_start + i * dest_stride;
+        if dest < src_start {
+            continue;
+        }
+        let diff = dest - src_start;
+        if src_stride == 0 {
+            // Every source element is the same one.
+            if diff == 0 {
+                forward_ok &= i == count - 1;
+                backward_ok &= i == 0;
+            }
+        } else if diff % src_stride == 0 && diff / src_stride < count {
+            let j = diff / src_stride;
+            forward_ok &= j <= i;
+            backward_ok &= j >= i;
+        }
+    }
+    (forward_ok, backward_ok)
+}
+
+#[cfg(test)]
+fn naive_copy_strided(
+    slice: &mut [u8],
+    src_start: usize,
+    src_stride: usize,
+    dest_start: usize,
+    dest_stride: usize,
+    count: usize,
+) {
+    let values: std::vec::Vec<u8> = (0..count)
+        .map(|i| slice[src_start + i * src_stride])
+        .collect();
+    for (i, value) in values.into_iter().enumerate() {
+        slice[dest_start + i * dest_stride] = value;
+    }
+}
+
+#[test]
+fn test_strided_against_naive() {
+    let len = 12;
+    let original: std::vec::Vec<u8> = (0..len as u8).collect();
+    let mut copied = 0;
+    for count in 1..=len {
+        for src_stride in 0..4 {
+            for dest_stride in 0..4 {
+                for src_start in 0..len {
+                    for dest_start in 0..len {
+                        let mut expected = original.clone();
+                        let mut buf = original.clone();
+                        let result = try_copy_strided_in_place(
+                            &mut buf,
+                            src_start,
+                            src_stride,
+                            dest_start,
+                            dest_stride,
+                            count,
+                        );
+                        let in_bounds = src_start + (count - 1) * src_stride < len
+                            && dest_start + (count - 1) * dest_stride < len;
+                        match result {
+                            Ok(()) => {
+                                naive_copy_strided(
+                                    &mut expected,
+                                    src_start,
+                                    src_stride,
+                                    dest_start,
+                                    dest_stride,
+                                    count,
+                                );
+                                assert_eq!(buf, expected);
+                                copied += 1;
+                            }
+                            Err(CopyError::NoSinglePassOrder) => {
+                                assert!(in_bounds);
+                                assert_eq!(buf, original);
+                            }
+                            Err(_) => {
+                                assert!(!in_bounds);
+                                assert_eq!(buf, original);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    assert!(copied > 0);
+}
+
+#[test]
+fn test_strided_no_single_pass_order() {
+    let mut values = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+    assert_eq!(
+        try_copy_strided_in_place(&mut values, 0, 2, 2, 1, 5),
+        Err(CopyError::NoSinglePassOrder)
+    );
+    assert_eq!(values, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    // With a zero source stride, a destination in the middle of the copy
+    // overwrites the only source element.
+    assert_eq!(
+        try_copy_strided_in_place(&mut values, 4, 0, 0, 2, 5),
+        Err(CopyError::NoSinglePassOrder)
+    );
+    copy_strided_in_place(&mut values, 8, 0, 0, 2, 5);
+    assert_eq!(values, [8, 1, 8, 3, 8, 5, 8, 7, 8]);
+}
+
+#[test]
+fn test_strided_errors() {
+    let mut values = [0u8; 6];
+    assert_eq!(
+        try_copy_strided_in_place(&mut values, 1, 2, 0, 1, 4),
+        Err(CopyError::StridedSrcOutOfBounds { last: 7, len: 6 })
+    );
+    assert_eq!(
+        try_copy_strided_in_place(&mut values, 0, 1, 2, 2, 3),
+        Err(CopyError::StridedDestOutOfBounds { last: 6, len: 6 })
+    );
+    assert_eq!(
+        try_copy_strided_in_place(&mut values, 0, usize::MAX, 0, 1, 3),
+        Err(CopyError::StridedOverflow {
+            start: 0,
+            stride: usize::MAX,
+            count: 3
+        })
+    );
+}
@@ -0,0 +1,148 @@
+use core::cmp;
+
+/// Transposes a row-major matrix in place.
+///
+/// `buf` holds a `rows` by `cols` matrix, one row after another. Afterwards it
+/// holds the `cols` by `rows` transpose, also row-major. This uses no heap
+/// allocation and a constant amount of extra memory.
+///
+/// Square matrices are transposed by swapping elements across the diagonal,
+/// one tile at a time to stay cache-friendly. Other shapes are transposed by
+/// following the cycles of the permutation. Finding where each cycle starts
+/// means walking it, so that path can take time quadratic in `buf.len()` in
+/// the worst case, where a transpose into a separate buffer is linear.
+///
+/// # Panics
+///
+/// This function will panic if `rows * cols` is not equal to `buf.len()`.
+///
+/// # Examples
+///
+/// ```
+/// # use copy_in_place::transpose_in_place;
+/// let mut matrix = [
+///     1, 2, 3,
+///     4, 5, 6,
+/// ];
+///
+/// transpose_in_place(&mut matrix, 2, 3);
+///
+/// assert_eq!(matrix, [
+///     1, 4,
+///     2, 5,
+///     3, 6,
+/// ]);
+/// ```
+pub fn transpose_in_place<T: Copy>(buf: &mut [T], rows: usize, cols: usize) {
+    assert!(
+        rows.checked_mul(cols) == Some(buf.len()),
+        "rows * cols is not the slice length"
+    );
+    if rows == cols {
+        transpose_square(buf, rows);
+    } else {
+        transpose_cycles(buf, rows, cols);
+    }
+}
+
+/// Transposes an `n` by `n` matrix by swapping each element above the
+/// diagonal with its mirror image, in tiles.
+fn transpose_square<T: Copy>(buf: &mut [T], n: usize) {
+    const TILE: usize = 16;
+    for tile_row in (0..n).step_by(TILE) {
+        for tile_col in (tile_row..n).step_by(TILE) {
+            for i in tile_row..cmp::min(tile_row + TILE, n) {
+                for j in cmp::max(tile_col, i + 1)..cmp::min(tile_col + TILE, n) {
+                    buf.swap(i * n + j, j * n + i);
+                }
+            }
+        }
+    }
+}
+
+/// Transposes a `rows` by `cols` row-major matrix by following the cycles of
+/// the permutation. Each cycle is rotated once, starting from its smallest
+/// index, which is found by walking the cycle.
+fn transpose_cycles<T: Copy>(buf: &mut [T], rows: usize, cols: usize) {
+    if rows <= 1 || cols <= 1 {
+        return;
+    }
+    // The element at row `i / cols` and column `i % cols` moves to row
+    // `i % cols` and column `i / cols`. That's always less than `len`, so it
+    // can't overflow. The first and last elements stay put.
+    let last = buf.len() - 1;
+    let next = |i: usize| (i % cols) * rows + i / cols;
+    for start in 1..last {
+        let mut i = next(start);
+        while i > start {
+            i = next(i);
+        }
+        if i < start {
+            // Not the smallest index in its cycle.
+            continue;
+        }
+        let mut carried = buf[start];
+        let mut i = start;
+        loop {
+            i = next(i);
+            core::mem::swap(&mut carried, &mut buf[i]);
+            if i == start {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn naive_transpose(buf: &[u32], rows: usize, cols: usize) -> std::vec::Vec<u32> {
+    let mut transposed = std::vec![0; buf.len()];
+    for r in 0..rows {
+        for c in 0..cols {
+            transposed[c * rows + r] = buf[r * cols + c];
+        }
+    }
+    transposed
+}
+
+#[test]
+fn test_transpose_against_naive() {
+    for rows in 0..=24 {
+        for cols in 0..=24 {
+            let original: std::vec::Vec<u32> = (0..(rows * cols) as u32).collect();
+            let mut buf = original.clone();
+            transpose_in_place(&mut buf, rows, cols);
+            assert_eq!(
+                buf,
+                naive_transpose(&original, rows, cols),
+                "{}x{}",
+                rows,
+                cols
+            );
+            transpose_in_place(&mut buf, cols, rows);
+            assert_eq!(buf, original, "{}x{}", rows, cols);
+        }
+    }
+}
+
+#[test]
+fn test_transpose_large_shapes() {
+    for &(rows, cols) in &[(37, 41), (100, 3), (64, 64), (65, 65), (1, 500)] {
+        let original: std::vec::Vec<u32> = (0..(rows * cols) as u32).collect();
+        let mut buf = original.clone();
+        transpose_in_place(&mut buf, rows, cols);
+        assert_eq!(
+            buf,
+            naive_transpose(&original, rows, cols),
+            "{}x{}",
+            rows,
+            cols
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "rows * cols is not the slice length")]
+fn test_transpose_bad_shape() {
+    let mut buf = [0u8; 6];
+    transpose_in_place(&mut buf, 2, 4);
+}